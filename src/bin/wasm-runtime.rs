@@ -1,7 +1,15 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
-use wasm_runtime::{CacheManager, Language, RuntimeLoader};
+use dialoguer::{Confirm, Select};
+use std::io::IsTerminal;
+#[cfg(feature = "wasmtime")]
+use std::path::PathBuf;
+#[cfg(feature = "wasmtime")]
+use wasm_runtime::{Engine, RunOptions};
+use wasm_runtime::{
+    CacheManager, LanguageRegistry, LockFile, LockedRuntime, PinFile, RuntimeLoader, WasmhubConfig,
+};
 
 #[derive(Parser)]
 #[command(name = "wasm-runtime")]
@@ -15,12 +23,14 @@ struct Cli {
 enum Commands {
     /// Download a runtime (or get from cache)
     Get {
-        /// Language (nodejs, python, ruby, php, go, rust)
+        /// Language slug or alias (e.g. python, py, nodejs, or a custom
+        /// registered runtime)
         language: String,
 
-        /// Version (use 'latest' or 'lts' for auto-selection)
-        #[arg(default_value = "latest")]
-        version: String,
+        /// Version (use 'latest' or 'lts' for auto-selection). Defaults to
+        /// the nearest `.wasm-runtime` pin, falling back to an interactive
+        /// selection ('latest' when stdin isn't a terminal).
+        version: Option<String>,
 
         /// Force re-download even if cached
         #[arg(short, long)]
@@ -35,7 +45,8 @@ enum Commands {
 
     /// Show detailed information about a runtime
     Info {
-        /// Language (nodejs, python, ruby, php, go, rust)
+        /// Language slug or alias (e.g. python, py, nodejs, or a custom
+        /// registered runtime)
         language: String,
 
         /// Version (optional, shows info for specific version)
@@ -47,6 +58,58 @@ enum Commands {
         #[command(subcommand)]
         action: CacheAction,
     },
+
+    /// Execute a cached runtime under a WASI sandbox
+    #[cfg(feature = "wasmtime")]
+    Run {
+        /// Language slug or alias (e.g. python, py, nodejs, or a custom
+        /// registered runtime)
+        language: String,
+
+        /// Version (use 'latest' or 'lts' for auto-selection). Defaults to
+        /// the nearest `.wasm-runtime` pin, falling back to an interactive
+        /// selection ('latest' when stdin isn't a terminal).
+        version: Option<String>,
+
+        /// Script or entrypoint passed to the guest runtime
+        script: Option<String>,
+
+        /// Host directory to preopen for the guest, as `host::guest`
+        /// (repeatable)
+        #[arg(long = "dir")]
+        dirs: Vec<String>,
+
+        /// Extra arguments passed through to the guest program
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Pin a runtime version for this project (or globally)
+    Pin {
+        /// Language slug or alias (e.g. python, py, nodejs, or a custom
+        /// registered runtime)
+        language: String,
+
+        /// Version to pin
+        version: String,
+
+        /// Write the pin to the global fallback file instead of the
+        /// current directory's `.wasm-runtime`
+        #[arg(short, long)]
+        global: bool,
+    },
+
+    /// Install the runtimes listed in wasmhub.toml's [runtimes], pinning
+    /// them to a wasmhub.lock for reproducible installs
+    Sync {
+        /// Re-resolve every constraint instead of installing the existing
+        /// wasmhub.lock
+        #[arg(long)]
+        update: bool,
+    },
+
+    /// Print an environment report to triage a broken or stale install
+    Doctor,
 }
 
 #[derive(Subcommand)]
@@ -56,7 +119,8 @@ enum CacheAction {
 
     /// Clear cache for a specific runtime
     Clear {
-        /// Language (nodejs, python, ruby, php, go, rust)
+        /// Language slug or alias (e.g. python, py, nodejs, or a custom
+        /// registered runtime)
         language: String,
 
         /// Version
@@ -84,13 +148,240 @@ async fn main() -> Result<()> {
         Commands::List { language } => handle_list(language).await,
         Commands::Info { language, version } => handle_info(language, version).await,
         Commands::Cache { action } => handle_cache(action),
+        #[cfg(feature = "wasmtime")]
+        Commands::Run {
+            language,
+            version,
+            script,
+            dirs,
+            args,
+        } => handle_run(language, version, script, dirs, args).await,
+        Commands::Pin {
+            language,
+            version,
+            global,
+        } => handle_pin(language, version, global),
+        Commands::Sync { update } => handle_sync(update).await,
+        Commands::Doctor => handle_doctor().await,
+    }
+}
+
+/// Resolves the version argument a user passed (or didn't): an explicit
+/// value wins, otherwise the nearest `.wasm-runtime` pin (project, then
+/// global) is used. Returns `None` if neither pins `language`, leaving the
+/// caller to decide a default.
+fn resolve_pinned_version(language: &str, version: Option<String>) -> Result<Option<String>> {
+    if let Some(version) = version {
+        return Ok(Some(version));
+    }
+    let cwd = std::env::current_dir()?;
+    PinFile::resolve(&cwd, language)
+}
+
+/// Resolves the version argument the same way [`resolve_pinned_version`]
+/// does, but when neither an explicit version nor a pin is available,
+/// prompts an interactive [`Select`] over the manifest's published versions
+/// instead of silently defaulting to `"latest"`. Falls back to `"latest"`
+/// without prompting when stdin isn't a terminal (e.g. in CI).
+async fn resolve_version_interactive(
+    loader: &RuntimeLoader,
+    language: &str,
+    version: Option<String>,
+) -> Result<String> {
+    if let Some(version) = resolve_pinned_version(language, version)? {
+        return Ok(version);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Ok("latest".to_string());
+    }
+
+    let manifest = loader.list_available().await?;
+    let info = manifest
+        .languages
+        .get(language)
+        .context(format!("No manifest found for {language}"))?;
+
+    let mut items = vec!["latest".to_string()];
+    if info.lts.is_some() {
+        items.push("lts".to_string());
     }
+    items.extend(info.versions.iter().cloned());
+
+    let selection = Select::new()
+        .with_prompt(format!("Select a {language} version"))
+        .items(&items)
+        .default(0)
+        .interact()
+        .context("Failed to read version selection")?;
+
+    Ok(items[selection].clone())
+}
+
+fn handle_pin(language_str: String, version: String, global: bool) -> Result<()> {
+    let registry = LanguageRegistry::with_builtins();
+    let language = registry.resolve(&language_str).unwrap_or(&language_str);
+
+    let path = if global {
+        PinFile::global_path()?
+    } else {
+        std::env::current_dir()?.join(wasm_runtime::pin::PIN_FILE_NAME)
+    };
+
+    let mut pin = if path.is_file() {
+        PinFile::load(&path)?
+    } else {
+        PinFile::default()
+    };
+    pin.set(language, &version);
+    pin.save(&path)?;
+
+    println!(
+        "{} {} {} in {}",
+        "Pinned".green().bold(),
+        language,
+        version,
+        path.display()
+    );
+
+    Ok(())
 }
 
-async fn handle_get(language_str: String, version: String, force: bool) -> Result<()> {
-    let language: Language = language_str
-        .parse()
-        .map_err(|e: String| anyhow::anyhow!(e))?;
+/// Whether a constraint string should be resolved as a semver range (via
+/// `get_runtime_matching`) rather than an exact/loose version spec (via
+/// `get_runtime`). A bare version like `"3.11.7"` is also valid semver-req
+/// syntax but is meant literally, so only treat strings with explicit range
+/// syntax as ranges.
+fn is_semver_range(constraint: &str) -> bool {
+    constraint
+        .chars()
+        .any(|c| matches!(c, '^' | '~' | '<' | '>' | '=' | ',' | '*'))
+}
+
+async fn handle_sync(update: bool) -> Result<()> {
+    let config = WasmhubConfig::load_default()?;
+    let runtimes = config
+        .runtimes
+        .context("No [runtimes] found in wasmhub.toml")?;
+
+    let lock_path = std::env::current_dir()?.join(wasm_runtime::lock::LOCK_FILE_NAME);
+    let loader = RuntimeLoader::builder().show_progress(true).build()?;
+    let cache = CacheManager::new()?;
+    let registry = LanguageRegistry::with_builtins();
+
+    let existing_lock = if update {
+        None
+    } else {
+        LockFile::load_if_exists(&lock_path)?
+    };
+
+    let mut lock = LockFile::default();
+
+    if let Some(existing_lock) = existing_lock {
+        println!(
+            "{} {} locked runtime(s) from {}...",
+            "Installing".cyan().bold(),
+            existing_lock.runtimes.len(),
+            wasm_runtime::lock::LOCK_FILE_NAME
+        );
+
+        for locked in &existing_lock.runtimes {
+            let cached = cache.get(&locked.language, &locked.version);
+            let runtime = match cached {
+                Some(runtime) if runtime.sha256 == locked.sha256 => runtime,
+                Some(_) => {
+                    cache.clear(&locked.language, &locked.version)?;
+                    loader
+                        .download_runtime(&locked.language, &locked.version)
+                        .await?
+                }
+                None => {
+                    loader
+                        .download_runtime(&locked.language, &locked.version)
+                        .await?
+                }
+            };
+
+            if runtime.sha256 != locked.sha256 {
+                bail!(
+                    "{} {}: downloaded sha256 {} does not match locked sha256 {}",
+                    locked.language,
+                    locked.version,
+                    runtime.sha256,
+                    locked.sha256
+                );
+            }
+
+            println!("  {} {} {}", "✓".green(), locked.language, locked.version);
+            lock.set(locked.clone());
+        }
+    } else {
+        println!(
+            "{} {} runtime constraint(s)...",
+            "Resolving".cyan().bold(),
+            runtimes.len()
+        );
+
+        for (language_str, constraint) in &runtimes {
+            let language = registry.resolve(language_str).unwrap_or(language_str);
+
+            let runtime = if is_semver_range(constraint) {
+                let req = constraint
+                    .parse()
+                    .context(format!("Invalid semver range \"{constraint}\" for {language}"))?;
+                loader.get_runtime_matching(language, &req).await?
+            } else {
+                loader.get_runtime(language, constraint).await?
+            };
+
+            println!(
+                "  {} {} {} {}",
+                "✓".green(),
+                language,
+                runtime.version,
+                format!("({constraint})").dimmed()
+            );
+
+            lock.set(LockedRuntime {
+                language: runtime.language.clone(),
+                version: runtime.version.clone(),
+                file: runtime.filename(),
+                size: runtime.size,
+                sha256: runtime.sha256.clone(),
+            });
+        }
+    }
+
+    lock.save(&lock_path)?;
+    println!(
+        "\n{} {}",
+        "Wrote".green().bold(),
+        lock_path.display()
+    );
+
+    if let Some(custom_runtimes) = &config.custom_runtimes {
+        if !custom_runtimes.is_empty() {
+            println!(
+                "\n{} {} custom runtime(s)...",
+                "Installing".cyan().bold(),
+                custom_runtimes.len()
+            );
+            let installed = loader.install_custom_runtimes().await?;
+            for runtime in &installed {
+                println!("  {} {} {}", "✓".green(), runtime.language, runtime.version);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_get(language_str: String, version: Option<String>, force: bool) -> Result<()> {
+    let registry = LanguageRegistry::with_builtins();
+    let language = registry.resolve(&language_str).unwrap_or(&language_str);
+
+    let loader = RuntimeLoader::builder().show_progress(true).build()?;
+    let version = resolve_version_interactive(&loader, language, version).await?;
 
     println!(
         "{} {} runtime (version: {})...",
@@ -99,23 +390,64 @@ async fn handle_get(language_str: String, version: String, force: bool) -> Resul
         version
     );
 
-    let loader = RuntimeLoader::builder().show_progress(true).build()?;
+    let cache = CacheManager::new()?;
 
-    if force {
-        let cache = CacheManager::new()?;
-        if cache.get(language, &version).is_some() {
-            cache.clear(language, &version)?;
-            println!("{} cache", "Cleared".yellow());
-        }
+    // Mirrors `get_runtime`'s own resolution order: an exact/pinned version
+    // already cached under the literal key is used as-is (no manifest
+    // needed); only a symbolic (`"latest"`/`"lts"`) or loose (prefix/range)
+    // spec goes through `resolve_version`. So `actual_version` always ends
+    // up being the same concrete cache key `get_runtime` is about to read
+    // or write, whatever shape `version` came in as.
+    let actual_version = match cache.get(language, &version) {
+        Some(_) => version.clone(),
+        None => loader.resolve_version(language, &version).await?,
+    };
+
+    // Clear against the resolved version, not the possibly-symbolic or
+    // loose `version` — otherwise this clears a cache key that was never
+    // written and leaves the real cached file untouched.
+    if force && cache.get(language, &actual_version).is_some() {
+        cache.clear(language, &actual_version)?;
+        println!("{} cache", "Cleared".yellow());
     }
 
+    let runtime = loader.get_runtime(language, &actual_version).await?;
+
+    println!("\n{}", "Success!".green().bold());
+    println!("  {}: {}", "Language".bold(), runtime.language);
+    println!("  {}: {}", "Version".bold(), runtime.version);
+    println!("  {}: {}", "Path".bold(), runtime.path.display());
+    println!(
+        "  {}: {} MB",
+        "Size".bold(),
+        runtime.size as f64 / 1_048_576.0
+    );
+    println!("  {}: {}", "SHA256".bold(), runtime.sha256);
+
+    Ok(())
+}
+
+#[cfg(feature = "wasmtime")]
+async fn handle_run(
+    language_str: String,
+    version: Option<String>,
+    script: Option<String>,
+    dirs: Vec<String>,
+    args: Vec<String>,
+) -> Result<()> {
+    let registry = LanguageRegistry::with_builtins();
+    let language = registry.resolve(&language_str).unwrap_or(&language_str);
+
+    let loader = RuntimeLoader::builder().show_progress(true).build()?;
+    let version = resolve_version_interactive(&loader, language, version).await?;
+
     let actual_version = if version == "latest" {
         loader.get_latest_version(language).await?
     } else if version == "lts" {
         let manifest = loader.list_available().await?;
         let runtime_info = manifest
             .languages
-            .get(language.as_str())
+            .get(language)
             .context(format!("No manifest found for {language}"))?;
         runtime_info
             .lts
@@ -127,35 +459,52 @@ async fn handle_get(language_str: String, version: String, force: bool) -> Resul
 
     let runtime = loader.get_runtime(language, &actual_version).await?;
 
-    println!("\n{}", "Success!".green().bold());
-    println!("  {}: {}", "Language".bold(), runtime.language);
-    println!("  {}: {}", "Version".bold(), runtime.version);
-    println!("  {}: {}", "Path".bold(), runtime.path.display());
     println!(
-        "  {}: {} MB",
-        "Size".bold(),
-        runtime.size as f64 / 1_048_576.0
+        "{} {} {}...",
+        "Running".cyan().bold(),
+        language,
+        runtime.version
     );
-    println!("  {}: {}", "SHA256".bold(), runtime.sha256);
 
-    Ok(())
+    let mut options = RunOptions::for_language(language, script.as_deref());
+    options.args.extend(args);
+    options
+        .preopen_dirs
+        .push((std::env::current_dir()?, ".".to_string()));
+    for dir in dirs {
+        let (host, guest) = dir
+            .split_once("::")
+            .context("--dir expects host::guest, e.g. --dir ./data::/data")?;
+        options
+            .preopen_dirs
+            .push((PathBuf::from(host), guest.to_string()));
+    }
+
+    let engine = Engine::new(&runtime)?;
+    let exit_code = engine.run(options)?;
+
+    std::process::exit(exit_code);
 }
 
 async fn handle_list(language_filter: Option<String>) -> Result<()> {
     println!("{} available runtimes...\n", "Fetching".cyan().bold());
 
-    let loader = RuntimeLoader::new()?;
+    let loader = RuntimeLoader::builder().build()?;
     let manifest = loader.list_available().await?;
+    let registry = LanguageRegistry::with_builtins();
 
-    let languages: Vec<Language> = if let Some(lang_str) = language_filter {
-        let lang: Language = lang_str.parse().map_err(|e: String| anyhow::anyhow!(e))?;
-        vec![lang]
+    let languages: Vec<String> = if let Some(lang_str) = language_filter {
+        vec![registry.resolve(&lang_str).unwrap_or(&lang_str).to_string()]
     } else {
-        Language::all().to_vec()
+        registry
+            .all()
+            .iter()
+            .map(|descriptor| descriptor.slug.clone())
+            .collect()
     };
 
     for language in languages {
-        if let Some(info) = manifest.languages.get(language.as_str()) {
+        if let Some(info) = manifest.languages.get(&language) {
             println!("{}", format!("{language}:").green().bold());
             println!("  {}: {}", "Latest".bold(), info.latest);
             if let Some(lts) = &info.lts {
@@ -192,16 +541,15 @@ async fn handle_list(language_filter: Option<String>) -> Result<()> {
 }
 
 async fn handle_info(language_str: String, version: Option<String>) -> Result<()> {
-    let language: Language = language_str
-        .parse()
-        .map_err(|e: String| anyhow::anyhow!(e))?;
+    let registry = LanguageRegistry::with_builtins();
+    let language = registry.resolve(&language_str).unwrap_or(&language_str);
 
-    let loader = RuntimeLoader::new()?;
+    let loader = RuntimeLoader::builder().build()?;
     let manifest = loader.list_available().await?;
 
     let info = manifest
         .languages
-        .get(language.as_str())
+        .get(language)
         .context(format!("No information found for {language}"))?;
 
     println!("\n{} {}\n", "Runtime Info:".cyan().bold(), language);
@@ -212,6 +560,20 @@ async fn handle_info(language_str: String, version: Option<String>) -> Result<()
     println!("  {}: {}", "Source".bold(), &info.source);
     println!("  {}: {}", "License".bold(), &info.license);
 
+    let version = match version {
+        Some(version) => Some(version),
+        None if std::io::stdin().is_terminal() && !info.versions.is_empty() => {
+            let selection = Select::new()
+                .with_prompt(format!("Select a {language} version for details"))
+                .items(&info.versions)
+                .default(0)
+                .interact()
+                .context("Failed to read version selection")?;
+            Some(info.versions[selection].clone())
+        }
+        None => None,
+    };
+
     if let Some(ver) = version {
         println!("\n{} {}:\n", "Version Details for".cyan().bold(), ver);
 
@@ -276,6 +638,18 @@ fn handle_cache(action: CacheAction) -> Result<()> {
                         runtime.version.yellow(),
                         format!("({:.2} MB)", runtime.size as f64 / 1_048_576.0).dimmed()
                     );
+
+                    if let Some(provenance) = cache.get_provenance(&runtime.language, &runtime.version) {
+                        println!(
+                            "        {} {}{}",
+                            "source:".dimmed(),
+                            provenance.source.dimmed(),
+                            provenance
+                                .revision
+                                .map(|rev| format!(" @ {rev}").dimmed().to_string())
+                                .unwrap_or_default()
+                        );
+                    }
                 }
 
                 println!(
@@ -291,13 +665,14 @@ fn handle_cache(action: CacheAction) -> Result<()> {
         }
 
         CacheAction::Clear { language, version } => {
-            let lang: Language = language.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            let registry = LanguageRegistry::with_builtins();
+            let language = registry.resolve(&language).unwrap_or(&language);
 
-            cache.clear(lang, &version)?;
+            cache.clear(language, &version)?;
             println!(
                 "{} cache for {} {}",
                 "Cleared".green().bold(),
-                lang,
+                language,
                 version
             );
             Ok(())
@@ -305,14 +680,13 @@ fn handle_cache(action: CacheAction) -> Result<()> {
 
         CacheAction::ClearAll { yes } => {
             if !yes {
-                print!("Are you sure you want to clear all cached runtimes? (y/N): ");
-                use std::io::Write;
-                std::io::stdout().flush()?;
-
-                let mut response = String::new();
-                std::io::stdin().read_line(&mut response)?;
+                let confirmed = Confirm::new()
+                    .with_prompt("Are you sure you want to clear all cached runtimes?")
+                    .default(false)
+                    .interact()
+                    .context("Failed to read confirmation")?;
 
-                if !response.trim().eq_ignore_ascii_case("y") {
+                if !confirmed {
                     println!("{}", "Cancelled".yellow());
                     return Ok(());
                 }
@@ -324,3 +698,102 @@ fn handle_cache(action: CacheAction) -> Result<()> {
         }
     }
 }
+
+/// An environment report in the spirit of `tauri info`/`millennium info`:
+/// host platform, cache usage, per-runtime integrity, manifest source
+/// reachability, and which cached runtimes have a newer version published.
+async fn handle_doctor() -> Result<()> {
+    let cache = CacheManager::new()?;
+    let loader = RuntimeLoader::new()?;
+
+    println!("\n{}\n", "wasm-runtime doctor".cyan().bold());
+
+    println!("{}", "Host".bold());
+    println!("  {}: {}", "OS".bold(), std::env::consts::OS);
+    println!("  {}: {}", "Arch".bold(), std::env::consts::ARCH);
+
+    let cache_dir = CacheManager::default_cache_dir()?;
+    let usage = cache.usage()?;
+    println!("\n{}", "Cache".bold());
+    println!("  {}: {}", "Location".bold(), cache_dir.display());
+    println!(
+        "  {}: {:.2} MB",
+        "Total size".bold(),
+        usage.total_size as f64 / 1_048_576.0
+    );
+
+    let runtimes = cache.list()?;
+    println!("\n{} ({})", "Cached Runtimes".bold(), runtimes.len());
+    if runtimes.is_empty() {
+        println!("  {}", "No cached runtimes".yellow());
+    } else {
+        for runtime in &runtimes {
+            let status = if let Some(provenance) = cache.get_provenance(&runtime.language, &runtime.version) {
+                if provenance.sha256 == runtime.sha256 {
+                    "ok".green().to_string()
+                } else {
+                    "sha256 mismatch".red().to_string()
+                }
+            } else {
+                match loader.fetch_runtime_manifest(&runtime.language).await {
+                    Ok(manifest) => match manifest.get_version(&runtime.version) {
+                        Some(expected) if expected.sha256 == runtime.sha256 => {
+                            "ok".green().to_string()
+                        }
+                        Some(_) => "sha256 mismatch".red().to_string(),
+                        None => "not in manifest".yellow().to_string(),
+                    },
+                    Err(_) => "manifest unreachable".yellow().to_string(),
+                }
+            };
+            println!(
+                "  {} {} {} {}",
+                "•".green(),
+                runtime.language.cyan(),
+                runtime.version.yellow(),
+                format!("({status})").dimmed()
+            );
+        }
+    }
+
+    println!("\n{}", "Manifest Sources".bold());
+    for source in loader.sources() {
+        let reachable = loader.check_source_reachability(source).await;
+        let status = if reachable {
+            "reachable".green()
+        } else {
+            "unreachable".red()
+        };
+        println!("  {} {}", source.describe().cyan(), status);
+    }
+
+    println!("\n{}", "Updates".bold());
+    let mut languages: Vec<&str> = runtimes.iter().map(|r| r.language.as_str()).collect();
+    languages.sort_unstable();
+    languages.dedup();
+
+    let mut any_outdated = false;
+    for language in languages {
+        let Ok(latest) = loader.get_latest_version(language).await else {
+            continue;
+        };
+        for runtime in runtimes.iter().filter(|r| r.language == language) {
+            if runtime.version != latest {
+                any_outdated = true;
+                println!(
+                    "  {} {}: cached {} {} latest {}",
+                    "•".yellow(),
+                    language.cyan(),
+                    runtime.version.yellow(),
+                    "<".dimmed(),
+                    latest.green()
+                );
+            }
+        }
+    }
+    if !any_outdated {
+        println!("  {}", "All cached runtimes are up to date".green());
+    }
+
+    Ok(())
+}
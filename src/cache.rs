@@ -1,22 +1,73 @@
 use crate::error::{Error, Result};
-use crate::runtime::{Language, Runtime};
+use crate::manifest::RuntimeVersion;
+use crate::runtime::Runtime;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use wasmparser::{Encoding, Operator, Parser, Payload};
+
+/// A per-language and total breakdown of on-disk cache usage, as reported by
+/// [`CacheManager::usage`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub total_size: u64,
+    pub by_language: HashMap<String, u64>,
+}
+
+/// The result of parsing a WASM binary's header, import table, and code
+/// section with [`CacheManager::inspect`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WasmInspection {
+    pub is_component: bool,
+    pub wasi: bool,
+    pub features: Vec<String>,
+}
+
+/// Where a cached runtime actually came from, recorded for entries
+/// registered via `crate::custom::CustomRuntime` so `CacheAction::Show` can
+/// report provenance instead of just a language/version/size triple.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeProvenance {
+    /// A short label for the source, e.g. `"git:<remote>@<revision>"` or
+    /// `"local:<path>"`.
+    pub source: String,
+    /// The exact commit resolved from a `Git` source's `revision`. `None`
+    /// for a `Local` source, which has no revision to record.
+    pub revision: Option<String>,
+    pub sha256: String,
+}
 
 pub struct CacheManager {
     cache_dir: PathBuf,
+    max_size: Option<u64>,
 }
 
 impl CacheManager {
     pub fn new() -> Result<Self> {
         let cache_dir = Self::default_cache_dir()?;
-        Ok(Self { cache_dir })
+        Ok(Self {
+            cache_dir,
+            max_size: None,
+        })
     }
 
     pub fn with_cache_dir(cache_dir: PathBuf) -> Self {
-        Self { cache_dir }
+        Self {
+            cache_dir,
+            max_size: None,
+        }
+    }
+
+    /// Sets a byte budget for the cache. Once set, `store` evicts
+    /// least-recently-used entries (via [`CacheManager::enforce_budget`])
+    /// whenever the cache grows past this size.
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
     }
 
     pub fn default_cache_dir() -> Result<PathBuf> {
@@ -26,13 +77,30 @@ impl CacheManager {
         Ok(cache_dir)
     }
 
-    pub fn get_path(&self, language: Language, version: &str) -> PathBuf {
+    /// `language` is a registry slug (e.g. `"python"`, or any slug
+    /// registered via `LanguageRegistry`), not the built-in `Language`
+    /// enum, so the cache layout stays open to runtimes that aren't one of
+    /// the six built-ins.
+    pub fn get_path(&self, language: &str, version: &str) -> PathBuf {
         self.cache_dir
-            .join(language.as_str())
+            .join(language)
             .join(format!("{version}.wasm"))
     }
 
-    pub fn get(&self, language: Language, version: &str) -> Option<Runtime> {
+    pub fn get(&self, language: &str, version: &str) -> Option<Runtime> {
+        let runtime = self.stat(language, version)?;
+        let _ = Self::touch_access_marker(&runtime.path);
+        Some(runtime)
+    }
+
+    /// Builds the [`Runtime`] for `language`/`version` from `fs::metadata`
+    /// alone, without touching the `.access` marker. Used by [`Self::list`]
+    /// (and everything that goes through it — [`Self::lru_order`],
+    /// [`Self::enforce_budget_except`], [`Self::usage`]) so merely
+    /// enumerating the cache can never overwrite the recency signal those
+    /// reads depend on; only [`Self::get`] and [`Self::store`], which
+    /// represent a real use of the runtime, touch the marker.
+    fn stat(&self, language: &str, version: &str) -> Option<Runtime> {
         let path = self.get_path(language, version);
         if !path.exists() {
             return None;
@@ -52,7 +120,7 @@ impl CacheManager {
         ))
     }
 
-    pub fn store(&self, language: Language, version: &str, data: &[u8]) -> Result<Runtime> {
+    pub fn store(&self, language: &str, version: &str, data: &[u8]) -> Result<Runtime> {
         let path = self.get_path(language, version);
 
         if let Some(parent) = path.parent() {
@@ -60,24 +128,144 @@ impl CacheManager {
         }
 
         fs::write(&path, data)?;
+        Self::touch_access_marker(&path)?;
 
         let size = data.len() as u64;
         let sha256 = Self::compute_sha256(&path)?;
 
-        Ok(Runtime::new(
-            language,
-            version.to_string(),
-            path,
-            size,
-            sha256,
-        ))
+        let runtime = Runtime::new(language, version.to_string(), path.clone(), size, sha256);
+
+        self.enforce_budget_except(Some(&path))?;
+
+        Ok(runtime)
     }
 
-    pub fn clear(&self, language: Language, version: &str) -> Result<()> {
+    /// Parses `data` as a WASM binary with `wasmparser`, confirming the
+    /// magic header and version, classifying it as a core module or a
+    /// component, detecting WASI imports, and collecting enabled proposals
+    /// (SIMD, threads, reference types, tail calls).
+    pub fn inspect(data: &[u8]) -> Result<WasmInspection> {
+        let mut inspection = WasmInspection::default();
+
+        for payload in Parser::new(0).parse_all(data) {
+            let payload = payload.map_err(|e| Error::InvalidWasm(e.to_string()))?;
+            match payload {
+                Payload::Version { encoding, .. } => {
+                    inspection.is_component = matches!(encoding, Encoding::Component);
+                }
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import.map_err(|e| Error::InvalidWasm(e.to_string()))?;
+                        if import.module == "wasi_snapshot_preview1" || import.module.starts_with("wasi:")
+                        {
+                            inspection.wasi = true;
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let mut operators = body
+                        .get_operators_reader()
+                        .map_err(|e| Error::InvalidWasm(e.to_string()))?;
+                    while !operators.eof() {
+                        let op = operators.read().map_err(|e| Error::InvalidWasm(e.to_string()))?;
+                        if let Some(feature) = Self::feature_for_operator(&op) {
+                            if !inspection.features.iter().any(|f| f == feature) {
+                                inspection.features.push(feature.to_string());
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(inspection)
+    }
+
+    fn feature_for_operator(op: &Operator) -> Option<&'static str> {
+        match op {
+            Operator::V128Load { .. } | Operator::I8x16Shuffle { .. } => Some("simd"),
+            Operator::MemoryAtomicWait32 { .. }
+            | Operator::MemoryAtomicWait64 { .. }
+            | Operator::AtomicFence => Some("threads"),
+            Operator::RefNull { .. } | Operator::RefFunc { .. } | Operator::RefIsNull => {
+                Some("reference-types")
+            }
+            Operator::ReturnCall { .. } | Operator::ReturnCallIndirect { .. } => {
+                Some("tail-calls")
+            }
+            _ => None,
+        }
+    }
+
+    /// Proposal names [`CacheManager::inspect`] can actually detect from an
+    /// opcode scan. A manifest is free to declare other proposals (e.g.
+    /// `bulk-memory`, `mutable-globals`, `multi-value`, `gc`) that this
+    /// scanner doesn't model yet; [`CacheManager::store_verified`] only
+    /// checks a declared feature against the scan when it's in this list,
+    /// so an honestly-declared-but-unmodeled proposal doesn't fail a
+    /// perfectly valid download.
+    const MODELED_FEATURES: &'static [&'static str] =
+        &["simd", "threads", "reference-types", "tail-calls"];
+
+    /// Stores `data` after checking it against `expected`'s declared
+    /// `wasi`/`features` via [`CacheManager::inspect`], rejecting a corrupt
+    /// or mislabeled download instead of caching it.
+    pub fn store_verified(
+        &self,
+        language: &str,
+        version: &str,
+        data: &[u8],
+        expected: &RuntimeVersion,
+    ) -> Result<Runtime> {
+        let inspection = Self::inspect(data)?;
+
+        if inspection.wasi != expected.wasi {
+            return Err(Error::MetadataMismatch(format!(
+                "manifest declares wasi={}, but the module {} WASI",
+                expected.wasi,
+                if inspection.wasi {
+                    "imports"
+                } else {
+                    "does not import"
+                }
+            )));
+        }
+
+        for feature in &expected.features {
+            // Only hold a declared feature against the scan if the scanner
+            // actually models it; an unmodeled-but-honestly-declared
+            // proposal (bulk-memory, mutable-globals, multi-value, gc, ...)
+            // can't be confirmed either way and shouldn't fail the download.
+            if !Self::MODELED_FEATURES.contains(&feature.as_str()) {
+                continue;
+            }
+            if !inspection.features.contains(feature) {
+                return Err(Error::MetadataMismatch(format!(
+                    "manifest declares feature \"{feature}\", but it was not detected in the module"
+                )));
+            }
+        }
+
+        self.store(language, version, data)
+    }
+
+    pub fn clear(&self, language: &str, version: &str) -> Result<()> {
         let path = self.get_path(language, version);
         if path.exists() {
-            fs::remove_file(path)?;
+            fs::remove_file(&path)?;
         }
+
+        let marker = Self::access_marker_path(&path);
+        if marker.exists() {
+            fs::remove_file(marker)?;
+        }
+
+        let provenance = self.provenance_path(language, version);
+        if provenance.exists() {
+            fs::remove_file(provenance)?;
+        }
+
         Ok(())
     }
 
@@ -88,6 +276,9 @@ impl CacheManager {
         Ok(())
     }
 
+    /// Walks the cache directory directly (one subdirectory per language
+    /// slug) rather than a fixed list of languages, so runtimes registered
+    /// through a `LanguageRegistry` are discovered too.
     pub fn list(&self) -> Result<Vec<Runtime>> {
         let mut runtimes = Vec::new();
 
@@ -95,11 +286,15 @@ impl CacheManager {
             return Ok(runtimes);
         }
 
-        for language in Language::all() {
-            let lang_dir = self.cache_dir.join(language.as_str());
-            if !lang_dir.exists() {
+        for lang_entry in fs::read_dir(&self.cache_dir)? {
+            let lang_entry = lang_entry?;
+            let lang_dir = lang_entry.path();
+            if !lang_dir.is_dir() {
                 continue;
             }
+            let Some(language) = lang_dir.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
 
             for entry in fs::read_dir(&lang_dir)? {
                 let entry = entry?;
@@ -114,7 +309,7 @@ impl CacheManager {
                     .and_then(|s| s.to_str())
                     .map(|s| s.to_string())
                 {
-                    if let Some(runtime) = self.get(*language, &version) {
+                    if let Some(runtime) = self.stat(language, &version) {
                         runtimes.push(runtime);
                     }
                 }
@@ -124,6 +319,143 @@ impl CacheManager {
         Ok(runtimes)
     }
 
+    /// Returns cached runtimes ordered from least- to most-recently used.
+    pub fn lru_order(&self) -> Result<Vec<Runtime>> {
+        let mut runtimes = self.list()?;
+        runtimes.sort_by_key(|runtime| Self::last_accessed(&runtime.path));
+        Ok(runtimes)
+    }
+
+    /// Deletes least-recently-used entries until the cache fits within
+    /// `max_size` (set via [`CacheManager::with_max_size`]). A no-op if no
+    /// budget was configured or the cache is already within it.
+    pub fn enforce_budget(&self) -> Result<()> {
+        self.enforce_budget_except(None)
+    }
+
+    fn enforce_budget_except(&self, protect: Option<&Path>) -> Result<()> {
+        let Some(max_size) = self.max_size else {
+            return Ok(());
+        };
+
+        let lru = self.lru_order()?;
+        let mut total: u64 = lru.iter().map(|runtime| runtime.size).sum();
+        if total <= max_size {
+            return Ok(());
+        }
+
+        for runtime in lru {
+            if total <= max_size {
+                break;
+            }
+            if protect == Some(runtime.path.as_path()) {
+                continue;
+            }
+
+            fs::remove_file(&runtime.path)?;
+            let marker = Self::access_marker_path(&runtime.path);
+            if marker.exists() {
+                fs::remove_file(marker)?;
+            }
+
+            total = total.saturating_sub(runtime.size);
+        }
+
+        Ok(())
+    }
+
+    /// Reports total and per-language on-disk cache usage.
+    pub fn usage(&self) -> Result<CacheUsage> {
+        let mut usage = CacheUsage::default();
+        for runtime in self.list()? {
+            usage.total_size += runtime.size;
+            *usage.by_language.entry(runtime.language).or_insert(0) += runtime.size;
+        }
+        Ok(usage)
+    }
+
+    fn access_marker_path(path: &Path) -> PathBuf {
+        let mut marker = path.as_os_str().to_os_string();
+        marker.push(".access");
+        PathBuf::from(marker)
+    }
+
+    fn touch_access_marker(path: &Path) -> Result<()> {
+        fs::write(Self::access_marker_path(path), [])?;
+        Ok(())
+    }
+
+    /// Recency of last access for `path`: prefers the sidecar access marker
+    /// (updated on every `get`/`store`) since atime is unreliable on many
+    /// mounts, falling back to the file's own atime/mtime.
+    fn last_accessed(path: &Path) -> SystemTime {
+        if let Ok(metadata) = fs::metadata(Self::access_marker_path(path)) {
+            if let Ok(modified) = metadata.modified() {
+                return modified;
+            }
+        }
+
+        fs::metadata(path)
+            .and_then(|metadata| metadata.accessed().or_else(|_| metadata.modified()))
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
+    /// Where a cached manifest JSON blob lives, keyed by `scope` (`"global"`
+    /// or a language slug), separate from the runtime artifacts themselves.
+    fn manifest_path(&self, scope: &str) -> PathBuf {
+        self.cache_dir.join("manifests").join(format!("{scope}.json"))
+    }
+
+    /// Returns the cached manifest text for `scope` alongside the time it
+    /// was fetched (the file's mtime), if a cached copy exists.
+    pub fn get_cached_manifest(&self, scope: &str) -> Option<(String, SystemTime)> {
+        let path = self.manifest_path(scope);
+        let data = fs::read_to_string(&path).ok()?;
+        let modified = fs::metadata(&path).ok()?.modified().ok()?;
+        Some((data, modified))
+    }
+
+    /// Stamps `data` as the manifest cached for `scope`, with the write
+    /// time serving as its fetched-at timestamp.
+    pub fn store_cached_manifest(&self, scope: &str, data: &str) -> Result<()> {
+        let path = self.manifest_path(scope);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Where a runtime's provenance sidecar lives, alongside its `.wasm`.
+    fn provenance_path(&self, language: &str, version: &str) -> PathBuf {
+        let mut path = self.get_path(language, version).into_os_string();
+        path.push(".provenance.json");
+        PathBuf::from(path)
+    }
+
+    /// Records `provenance` for a cached custom runtime, so
+    /// [`CacheManager::get_provenance`] (and `CacheAction::Show`) can report
+    /// where it actually came from.
+    pub fn store_provenance(
+        &self,
+        language: &str,
+        version: &str,
+        provenance: &RuntimeProvenance,
+    ) -> Result<()> {
+        let path = self.provenance_path(language, version);
+        let json = serde_json::to_string(provenance)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Returns the provenance recorded for `language`/`version`, if it was
+    /// cached via a `CustomRuntime` source.
+    pub fn get_provenance(&self, language: &str, version: &str) -> Option<RuntimeProvenance> {
+        let path = self.provenance_path(language, version);
+        let data = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
     pub fn compute_sha256(path: &PathBuf) -> Result<String> {
         let mut file = fs::File::open(path)?;
         let mut hasher = Sha256::new();
@@ -174,7 +506,7 @@ mod tests {
     #[test]
     fn test_get_path() {
         let (cache, _temp) = create_test_cache();
-        let path = cache.get_path(Language::Python, "3.11.7");
+        let path = cache.get_path("python", "3.11.7");
         assert!(path.to_string_lossy().contains("python"));
         assert!(path.to_string_lossy().contains("3.11.7.wasm"));
     }
@@ -184,14 +516,14 @@ mod tests {
         let (cache, _temp) = create_test_cache();
         let data = b"test wasm data";
         let runtime = cache
-            .store(Language::Python, "3.11.7", data)
+            .store("python", "3.11.7", data)
             .expect("Failed to store");
 
-        assert_eq!(runtime.language, Language::Python);
+        assert_eq!(runtime.language, "python");
         assert_eq!(runtime.version, "3.11.7");
         assert_eq!(runtime.size, data.len() as u64);
 
-        let retrieved = cache.get(Language::Python, "3.11.7");
+        let retrieved = cache.get("python", "3.11.7");
         assert!(retrieved.is_some());
         let retrieved = retrieved.unwrap();
         assert_eq!(retrieved.version, "3.11.7");
@@ -201,7 +533,7 @@ mod tests {
     #[test]
     fn test_get_nonexistent() {
         let (cache, _temp) = create_test_cache();
-        let result = cache.get(Language::Python, "3.11.7");
+        let result = cache.get("python", "3.11.7");
         assert!(result.is_none());
     }
 
@@ -210,15 +542,15 @@ mod tests {
         let (cache, _temp) = create_test_cache();
         let data = b"test wasm data";
         cache
-            .store(Language::Python, "3.11.7", data)
+            .store("python", "3.11.7", data)
             .expect("Failed to store");
 
-        assert!(cache.get(Language::Python, "3.11.7").is_some());
+        assert!(cache.get("python", "3.11.7").is_some());
 
         cache
-            .clear(Language::Python, "3.11.7")
+            .clear("python", "3.11.7")
             .expect("Failed to clear");
-        assert!(cache.get(Language::Python, "3.11.7").is_none());
+        assert!(cache.get("python", "3.11.7").is_none());
     }
 
     #[test]
@@ -226,16 +558,16 @@ mod tests {
         let (cache, _temp) = create_test_cache();
         let data = b"test wasm data";
         cache
-            .store(Language::Python, "3.11.7", data)
+            .store("python", "3.11.7", data)
             .expect("Failed to store");
         cache
-            .store(Language::Ruby, "3.2.2", data)
+            .store("ruby", "3.2.2", data)
             .expect("Failed to store");
 
         cache.clear_all().expect("Failed to clear all");
 
-        assert!(cache.get(Language::Python, "3.11.7").is_none());
-        assert!(cache.get(Language::Ruby, "3.2.2").is_none());
+        assert!(cache.get("python", "3.11.7").is_none());
+        assert!(cache.get("ruby", "3.2.2").is_none());
     }
 
     #[test]
@@ -244,10 +576,10 @@ mod tests {
         let data = b"test wasm data";
 
         cache
-            .store(Language::Python, "3.11.7", data)
+            .store("python", "3.11.7", data)
             .expect("Failed to store");
         cache
-            .store(Language::Ruby, "3.2.2", data)
+            .store("ruby", "3.2.2", data)
             .expect("Failed to store");
 
         let runtimes = cache.list().expect("Failed to list");
@@ -277,7 +609,7 @@ mod tests {
         let (cache, _temp) = create_test_cache();
         let data = b"test wasm data";
         let runtime = cache
-            .store(Language::Python, "3.11.7", data)
+            .store("python", "3.11.7", data)
             .expect("Failed to store");
 
         let result = cache.verify_integrity(&runtime, &runtime.sha256);
@@ -286,4 +618,240 @@ mod tests {
         let result = cache.verify_integrity(&runtime, "invalid_hash");
         assert!(result.is_err());
     }
+
+    const MINIMAL_MODULE: &[u8] = b"\0asm\x01\x00\x00\x00";
+
+    #[test]
+    fn test_inspect_minimal_module() {
+        let inspection = CacheManager::inspect(MINIMAL_MODULE).expect("Failed to inspect");
+        assert!(!inspection.is_component);
+        assert!(!inspection.wasi);
+        assert!(inspection.features.is_empty());
+    }
+
+    #[test]
+    fn test_inspect_rejects_garbage() {
+        let result = CacheManager::inspect(b"not a wasm module");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_store_verified_rejects_wasi_mismatch() {
+        let (cache, _temp) = create_test_cache();
+        let expected = RuntimeVersion::new(
+            "python-3.11.7.wasm".to_string(),
+            MINIMAL_MODULE.len() as u64,
+            "ignored".to_string(),
+            "2024-01-01".to_string(),
+            "https://example.com/python-3.11.7.wasm".to_string(),
+        )
+        .with_wasi(true);
+
+        let result =
+            cache.store_verified("python", "3.11.7", MINIMAL_MODULE, &expected);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_store_verified_accepts_matching_metadata() {
+        let (cache, _temp) = create_test_cache();
+        let expected = RuntimeVersion::new(
+            "python-3.11.7.wasm".to_string(),
+            MINIMAL_MODULE.len() as u64,
+            "ignored".to_string(),
+            "2024-01-01".to_string(),
+            "https://example.com/python-3.11.7.wasm".to_string(),
+        );
+
+        let result = cache
+            .store_verified("python", "3.11.7", MINIMAL_MODULE, &expected)
+            .expect("Failed to store");
+        assert_eq!(result.version, "3.11.7");
+    }
+
+    #[test]
+    fn test_store_verified_ignores_unmodeled_declared_feature() {
+        // "bulk-memory" isn't one of the proposals the opcode scanner
+        // models, so an honest manifest declaring it shouldn't fail the
+        // download just because the scan can't confirm it.
+        let (cache, _temp) = create_test_cache();
+        let mut expected = RuntimeVersion::new(
+            "python-3.11.7.wasm".to_string(),
+            MINIMAL_MODULE.len() as u64,
+            "ignored".to_string(),
+            "2024-01-01".to_string(),
+            "https://example.com/python-3.11.7.wasm".to_string(),
+        );
+        expected.add_feature("bulk-memory".to_string());
+
+        let result = cache
+            .store_verified("python", "3.11.7", MINIMAL_MODULE, &expected)
+            .expect("an unmodeled declared feature should not be rejected");
+        assert_eq!(result.version, "3.11.7");
+    }
+
+    #[test]
+    fn test_usage() {
+        let (cache, _temp) = create_test_cache();
+        cache
+            .store("python", "3.11.7", b"python data")
+            .expect("Failed to store");
+        cache
+            .store("ruby", "3.2.2", b"ruby data!!")
+            .expect("Failed to store");
+
+        let usage = cache.usage().expect("Failed to compute usage");
+        assert_eq!(usage.total_size, 11 + 11);
+        assert_eq!(usage.by_language.get("python"), Some(&11));
+        assert_eq!(usage.by_language.get("ruby"), Some(&11));
+    }
+
+    #[test]
+    fn test_lru_order() {
+        let (cache, _temp) = create_test_cache();
+        cache
+            .store("python", "3.11.7", b"older")
+            .expect("Failed to store");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cache
+            .store("ruby", "3.2.2", b"newer")
+            .expect("Failed to store");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        // Re-touch the first entry so it becomes the most recently used.
+        cache.get("python", "3.11.7");
+
+        let ordered = cache.lru_order().expect("Failed to compute lru order");
+        assert_eq!(ordered.len(), 2);
+        assert_eq!(ordered[0].version, "3.2.2");
+        assert_eq!(ordered[1].version, "3.11.7");
+    }
+
+    #[test]
+    fn test_list_does_not_disturb_recency() {
+        // Enumerating the cache (`list`/`lru_order`/`usage`) must not
+        // rewrite the `.access` marker, or every read would make all
+        // entries look equally fresh and defeat LRU eviction.
+        let (cache, _temp) = create_test_cache();
+        cache
+            .store("python", "3.11.7", b"older")
+            .expect("Failed to store");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cache
+            .store("ruby", "3.2.2", b"newer")
+            .expect("Failed to store");
+
+        let marker = CacheManager::access_marker_path(&cache.get_path("python", "3.11.7"));
+        let before = fs::metadata(&marker).unwrap().modified().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let _ = cache.list().expect("Failed to list");
+        let _ = cache.lru_order().expect("Failed to compute lru order");
+        let _ = cache.usage().expect("Failed to compute usage");
+
+        let after = fs::metadata(&marker).unwrap().modified().unwrap();
+        assert_eq!(before, after, "list()/lru_order()/usage() must not touch the access marker");
+
+        // python is still the least-recently-used entry even after all
+        // those reads, since none of them should have touched its marker.
+        let ordered = cache.lru_order().expect("Failed to compute lru order");
+        assert_eq!(ordered[0].version, "3.11.7");
+        assert_eq!(ordered[1].version, "3.2.2");
+    }
+
+    #[test]
+    fn test_enforce_budget_evicts_lru_but_not_new_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::with_cache_dir(temp_dir.path().to_path_buf()).with_max_size(10);
+
+        cache
+            .store("python", "3.11.7", b"0123456789")
+            .expect("Failed to store");
+
+        // Storing a second entry pushes the cache over budget; the
+        // least-recently-used entry (python) should be evicted, but the
+        // entry just created (ruby) must survive.
+        cache
+            .store("ruby", "3.2.2", b"abcdefghij")
+            .expect("Failed to store");
+
+        assert!(cache.get("python", "3.11.7").is_none());
+        assert!(cache.get("ruby", "3.2.2").is_some());
+    }
+
+    #[test]
+    fn test_cached_manifest_roundtrip() {
+        let (cache, _temp) = create_test_cache();
+        assert!(cache.get_cached_manifest("global").is_none());
+
+        cache
+            .store_cached_manifest("global", "{\"languages\":{}}")
+            .expect("Failed to store manifest");
+
+        let (raw, fetched_at) = cache
+            .get_cached_manifest("global")
+            .expect("manifest should be cached");
+        assert_eq!(raw, "{\"languages\":{}}");
+        assert!(fetched_at.elapsed().unwrap() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_provenance_roundtrip() {
+        let (cache, _temp) = create_test_cache();
+        cache
+            .store("zig", "0.1.0-nightly", b"custom wasm bytes")
+            .expect("Failed to store");
+
+        assert!(cache.get_provenance("zig", "0.1.0-nightly").is_none());
+
+        let provenance = RuntimeProvenance {
+            source: "git:https://example.com/zig-wasm.git@a1b2c3d".to_string(),
+            revision: Some("a1b2c3d4e5f6".to_string()),
+            sha256: "abc123".to_string(),
+        };
+        cache
+            .store_provenance("zig", "0.1.0-nightly", &provenance)
+            .expect("Failed to store provenance");
+
+        assert_eq!(
+            cache.get_provenance("zig", "0.1.0-nightly"),
+            Some(provenance)
+        );
+    }
+
+    #[test]
+    fn test_clear_removes_provenance() {
+        let (cache, _temp) = create_test_cache();
+        cache
+            .store("zig", "0.1.0-nightly", b"custom wasm bytes")
+            .expect("Failed to store");
+        cache
+            .store_provenance(
+                "zig",
+                "0.1.0-nightly",
+                &RuntimeProvenance {
+                    source: "local:/opt/zig.wasm".to_string(),
+                    revision: None,
+                    sha256: "abc123".to_string(),
+                },
+            )
+            .expect("Failed to store provenance");
+
+        cache.clear("zig", "0.1.0-nightly").expect("Failed to clear");
+
+        assert!(cache.get_provenance("zig", "0.1.0-nightly").is_none());
+    }
+
+    #[test]
+    fn test_with_max_size_noop_when_under_budget() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache =
+            CacheManager::with_cache_dir(temp_dir.path().to_path_buf()).with_max_size(1024);
+
+        cache
+            .store("python", "3.11.7", b"small")
+            .expect("Failed to store");
+
+        assert!(cache.get("python", "3.11.7").is_some());
+    }
 }
@@ -0,0 +1,217 @@
+//! Declarative `wasmhub.toml` configuration, loaded by
+//! [`crate::loader::RuntimeLoaderBuilder`] so source ordering and language
+//! selection can be driven without code changes, e.g.:
+//!
+//! ```toml
+//! cache_dir = "/var/cache/wasmhub"
+//!
+//! [[sources]]
+//! type = "git"
+//! remote = "https://example.com/python-wasm.git"
+//! revision = "a1b2c3d"
+//! subpath = "dist/python-3.11.7.wasm"
+//!
+//! [[sources]]
+//! type = "github-releases"
+//!
+//! [languages]
+//! only = ["python", "rust"]
+//!
+//! [runtimes]
+//! python = "^3.11"
+//! ruby = "latest"
+//!
+//! [[custom_runtimes]]
+//! language = "zig"
+//! version = "0.1.0-nightly"
+//! type = "git"
+//! remote = "https://example.com/zig-wasm.git"
+//! revision = "a1b2c3d"
+//! build = "cargo build --release --target wasm32-wasi"
+//! subpath = "target/wasm32-wasi/release/zig.wasm"
+//! ```
+//!
+//! File settings fill in whatever the builder didn't set programmatically;
+//! an explicit builder call always wins over the file, the same way a
+//! grammar/runtime manager lets code override its own declarative config.
+//! `[runtimes]` is consulted only by the CLI's `sync` command, which
+//! resolves each constraint and records the result in a `wasmhub.lock`.
+//! `[[custom_runtimes]]` entries are installed by the same command, via
+//! [`crate::loader::RuntimeLoader::install_custom_runtimes`].
+
+use crate::custom::CustomRuntime;
+use crate::error::{Error, Result};
+use crate::loader::RuntimeSource;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Restricts which languages are resolvable: either an allow-list (`only`)
+/// or a deny-list (`except`). Untagged so a config file writes whichever
+/// reads more naturally without an extra `kind` discriminator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum LanguageFilter {
+    Only { only: Vec<String> },
+    Except { except: Vec<String> },
+}
+
+impl LanguageFilter {
+    /// Whether `language` (a canonical slug) is resolvable under this filter.
+    pub fn allows(&self, language: &str) -> bool {
+        match self {
+            LanguageFilter::Only { only } => only.iter().any(|allowed| allowed == language),
+            LanguageFilter::Except { except } => !except.iter().any(|denied| denied == language),
+        }
+    }
+}
+
+/// Parsed contents of a `wasmhub.toml`. Every field is optional: an absent
+/// field means "let the builder's own default decide".
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WasmhubConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sources: Option<Vec<RuntimeSource>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub languages: Option<LanguageFilter>,
+    /// Required runtimes for `sync`, keyed by language slug, valued by a
+    /// version constraint (an exact version, `"latest"`/`"lts"`, or a
+    /// semver range like `"^3.11"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtimes: Option<BTreeMap<String, String>>,
+    /// Private or bleeding-edge runtimes not published in the central
+    /// manifest, installed by `sync` via
+    /// [`crate::loader::RuntimeLoader::install_custom_runtimes`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_runtimes: Option<Vec<CustomRuntime>>,
+}
+
+impl WasmhubConfig {
+    /// The default location checked by [`WasmhubConfig::load_default`]:
+    /// `wasmhub.toml` in the current directory.
+    pub fn default_path() -> PathBuf {
+        PathBuf::from("wasmhub.toml")
+    }
+
+    /// Parses a config from `path`, failing if it doesn't exist or isn't
+    /// valid TOML.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        toml::from_str(&contents)
+            .map_err(|e| Error::Other(format!("Invalid config at {}: {e}", path.display())))
+    }
+
+    /// Loads `wasmhub.toml` from the current directory if it exists,
+    /// otherwise returns an empty (all-defaults) config.
+    pub fn load_default() -> Result<Self> {
+        let path = Self::default_path();
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_filter_only() {
+        let filter = LanguageFilter::Only {
+            only: vec!["python".to_string(), "rust".to_string()],
+        };
+        assert!(filter.allows("python"));
+        assert!(!filter.allows("ruby"));
+    }
+
+    #[test]
+    fn test_language_filter_except() {
+        let filter = LanguageFilter::Except {
+            except: vec!["ruby".to_string()],
+        };
+        assert!(filter.allows("python"));
+        assert!(!filter.allows("ruby"));
+    }
+
+    #[test]
+    fn test_parse_config_toml() {
+        let toml = r#"
+            cache_dir = "/var/cache/wasmhub"
+
+            [[sources]]
+            type = "git"
+            remote = "https://example.com/python-wasm.git"
+            revision = "a1b2c3d"
+            subpath = "dist/python-3.11.7.wasm"
+
+            [[sources]]
+            type = "github-releases"
+
+            [languages]
+            only = ["python", "rust"]
+
+            [runtimes]
+            python = "^3.11"
+            ruby = "latest"
+        "#;
+
+        let config: WasmhubConfig = toml::from_str(toml).expect("Failed to parse config");
+        assert_eq!(config.cache_dir, Some(PathBuf::from("/var/cache/wasmhub")));
+        assert_eq!(config.sources.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            config.languages,
+            Some(LanguageFilter::Only {
+                only: vec!["python".to_string(), "rust".to_string()]
+            })
+        );
+        let runtimes = config.runtimes.expect("runtimes should be present");
+        assert_eq!(runtimes.get("python"), Some(&"^3.11".to_string()));
+        assert_eq!(runtimes.get("ruby"), Some(&"latest".to_string()));
+    }
+
+    #[test]
+    fn test_parse_config_toml_with_custom_runtimes() {
+        let toml = r#"
+            [[custom_runtimes]]
+            language = "zig"
+            version = "0.1.0-nightly"
+            type = "git"
+            remote = "https://example.com/zig-wasm.git"
+            revision = "a1b2c3d"
+            build = "make wasm"
+            subpath = "dist/zig.wasm"
+
+            [[custom_runtimes]]
+            language = "lua"
+            version = "5.4.6"
+            type = "local"
+            path = "/opt/lua-5.4.6.wasm"
+        "#;
+
+        let config: WasmhubConfig = toml::from_str(toml).expect("Failed to parse config");
+        let custom_runtimes = config
+            .custom_runtimes
+            .expect("custom_runtimes should be present");
+        assert_eq!(custom_runtimes.len(), 2);
+        assert_eq!(custom_runtimes[0].language, "zig");
+        assert_eq!(custom_runtimes[1].language, "lua");
+    }
+
+    #[test]
+    fn test_load_default_missing_file_is_empty() {
+        // Run in a scratch directory so a real wasmhub.toml elsewhere on
+        // the machine can't leak into the test.
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        let config = WasmhubConfig::load_default().expect("Failed to load default config");
+        assert_eq!(config, WasmhubConfig::default());
+
+        std::env::set_current_dir(original).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+//! Custom runtime sources declared in `wasmhub.toml`, modeled on Helix's
+//! grammar `GrammarSource` enum: a `Local` path to a prebuilt `.wasm`, or a
+//! `Git` remote pinned to an exact revision and optionally built from source
+//! before it's cached. This lets a team register a private or bleeding-edge
+//! runtime under its own language/version without waiting for the central
+//! manifest, e.g.:
+//!
+//! ```toml
+//! [[custom_runtimes]]
+//! language = "zig"
+//! version = "0.1.0-nightly"
+//! type = "git"
+//! remote = "https://example.com/zig-wasm.git"
+//! revision = "a1b2c3d"
+//! build = "cargo build --release --target wasm32-wasi"
+//! subpath = "target/wasm32-wasi/release/zig.wasm"
+//! ```
+
+use crate::error::{Error, Result};
+use crate::gitfetch::ShallowCheckout;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where a custom runtime's bytes come from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum CustomRuntimeSource {
+    /// A `.wasm` already on disk.
+    Local { path: PathBuf },
+    /// A specific commit in a git remote. Shallow-cloned into a scratch
+    /// directory, checked out at `revision`, optionally built with `build`,
+    /// then `subpath` (or `<language>-<version>.wasm` at the repo root if
+    /// unset) is read.
+    Git {
+        remote: String,
+        revision: String,
+        #[serde(default)]
+        subpath: Option<PathBuf>,
+        /// A shell command run in the checkout before `subpath` is read,
+        /// e.g. to compile the runtime from source. Runs whenever it is set,
+        /// even if the checkout already carries a built `.wasm`.
+        #[serde(default)]
+        build: Option<String>,
+    },
+}
+
+/// A single `[[custom_runtimes]]` entry: registers `language`/`version` as
+/// if it were published in the central manifest, sourced from `source`
+/// instead of a CDN.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomRuntime {
+    pub language: String,
+    pub version: String,
+    #[serde(flatten)]
+    pub source: CustomRuntimeSource,
+}
+
+impl CustomRuntime {
+    /// A short label identifying this entry's source, recorded alongside
+    /// its resolved revision and sha256 as [`crate::cache::RuntimeProvenance`]
+    /// so `CacheAction::Show` can report where a cached runtime came from.
+    pub fn describe_source(&self) -> String {
+        match &self.source {
+            CustomRuntimeSource::Local { path } => format!("local:{}", path.display()),
+            CustomRuntimeSource::Git { remote, revision, .. } => {
+                format!("git:{remote}@{revision}")
+            }
+        }
+    }
+
+    /// Fetches (and, for a `Git` source, builds) this runtime's bytes,
+    /// returning them alongside the resolved git revision if the source was
+    /// a `Git` entry (`None` for `Local`, which has no revision to record).
+    pub fn resolve(&self) -> Result<(Vec<u8>, Option<String>)> {
+        match &self.source {
+            CustomRuntimeSource::Local { path } => {
+                let data = std::fs::read(path)?;
+                Ok((data, None))
+            }
+            CustomRuntimeSource::Git {
+                remote,
+                revision,
+                subpath,
+                build,
+            } => {
+                let (data, resolved_revision) = self.fetch_and_build_from_git(
+                    remote,
+                    revision,
+                    subpath.as_deref(),
+                    build.as_deref(),
+                )?;
+                Ok((data, Some(resolved_revision)))
+            }
+        }
+    }
+
+    fn fetch_and_build_from_git(
+        &self,
+        remote: &str,
+        revision: &str,
+        subpath: Option<&Path>,
+        build: Option<&str>,
+    ) -> Result<(Vec<u8>, String)> {
+        let checkout = ShallowCheckout::fetch(
+            &format!(
+                "wasm-runtime-custom-{}-{}-{revision}",
+                self.language, self.version
+            ),
+            remote,
+            revision,
+        )?;
+
+        if let Some(build) = build {
+            let status = Command::new("sh")
+                .args(["-c", build])
+                .current_dir(&checkout.dir)
+                .status()?;
+            if !status.success() {
+                return Err(Error::Other(format!(
+                    "build command `{build}` failed with status {status}"
+                )));
+            }
+        }
+
+        let file_path = match subpath {
+            Some(subpath) => checkout.join(subpath),
+            None => checkout.join(Path::new(&format!("{}-{}.wasm", self.language, self.version))),
+        };
+        let data = std::fs::read(&file_path)?;
+
+        Ok((data, checkout.resolved_revision.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_source_describe() {
+        let entry = CustomRuntime {
+            language: "lua".to_string(),
+            version: "5.4.6".to_string(),
+            source: CustomRuntimeSource::Local {
+                path: PathBuf::from("/opt/lua-5.4.6.wasm"),
+            },
+        };
+        assert_eq!(entry.describe_source(), "local:/opt/lua-5.4.6.wasm");
+    }
+
+    #[test]
+    fn test_git_source_describe() {
+        let entry = CustomRuntime {
+            language: "zig".to_string(),
+            version: "0.1.0-nightly".to_string(),
+            source: CustomRuntimeSource::Git {
+                remote: "https://example.com/zig-wasm.git".to_string(),
+                revision: "a1b2c3d".to_string(),
+                subpath: None,
+                build: None,
+            },
+        };
+        assert_eq!(
+            entry.describe_source(),
+            "git:https://example.com/zig-wasm.git@a1b2c3d"
+        );
+    }
+
+    #[test]
+    fn test_local_source_resolve_reads_file() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let wasm_path = temp_dir.path().join("lua-5.4.6.wasm");
+        std::fs::write(&wasm_path, b"local wasm bytes").unwrap();
+
+        let entry = CustomRuntime {
+            language: "lua".to_string(),
+            version: "5.4.6".to_string(),
+            source: CustomRuntimeSource::Local {
+                path: wasm_path,
+            },
+        };
+
+        let (data, revision) = entry.resolve().expect("local source should resolve");
+        assert_eq!(data, b"local wasm bytes");
+        assert_eq!(revision, None);
+    }
+
+    #[test]
+    fn test_custom_runtime_serde_roundtrip() {
+        let entry = CustomRuntime {
+            language: "zig".to_string(),
+            version: "0.1.0-nightly".to_string(),
+            source: CustomRuntimeSource::Git {
+                remote: "https://example.com/zig-wasm.git".to_string(),
+                revision: "a1b2c3d".to_string(),
+                subpath: Some(PathBuf::from("dist/zig.wasm")),
+                build: Some("make wasm".to_string()),
+            },
+        };
+
+        let toml = toml::to_string(&entry).unwrap();
+        let parsed: CustomRuntime = toml::from_str(&toml).unwrap();
+        assert_eq!(entry, parsed);
+    }
+}
@@ -0,0 +1,227 @@
+//! Execution of cached WASM runtimes via Wasmtime + WASI.
+//!
+//! This module is gated behind the `wasmtime` cargo feature. It turns a
+//! downloaded-and-cached [`Runtime`] into something that can actually be
+//! run, analogous to how editor extension hosts run WASM language servers
+//! inside a WASI sandbox.
+
+use crate::cache::CacheManager;
+use crate::error::{Error, Result};
+use crate::runtime::Runtime;
+use std::path::PathBuf;
+use wasmtime::component::{Component, Linker as ComponentLinker};
+use wasmtime::{Config, Engine as WasmtimeEngine, Linker, Module, Store};
+use wasmtime_wasi::sync::{Dir, WasiCtxBuilder};
+use wasmtime_wasi::{I32Exit, WasiCtx};
+
+/// Where a WASI stream should read from or write to.
+#[derive(Debug, Clone)]
+pub enum Stdio {
+    /// Inherit the host process's stream.
+    Inherit,
+    /// Discard writes / read EOF immediately.
+    Null,
+    /// Redirect to a file on disk.
+    File(PathBuf),
+}
+
+impl Default for Stdio {
+    fn default() -> Self {
+        Stdio::Inherit
+    }
+}
+
+/// Options controlling how a cached runtime is executed.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub preopen_dirs: Vec<(PathBuf, String)>,
+    pub stdin: Stdio,
+    pub stdout: Stdio,
+    pub stderr: Stdio,
+}
+
+impl RunOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds sensible defaults for `language` (a registry slug, e.g.
+    /// `"python"` or a custom runtime's slug), e.g. passing `script` as argv
+    /// for languages that expect a file to run.
+    pub fn for_language(language: &str, script: Option<&str>) -> Self {
+        let mut options = Self::default();
+        // `WasiCtxBuilder::args` takes the guest's *complete* argv,
+        // including argv[0]; an interpreter reads argv[0] as its own
+        // program name and the rest as its arguments, so `script` must come
+        // after a program-name placeholder or the interpreter sees no
+        // script to run at all.
+        options.args.push(language.to_string());
+        if let Some(script) = script {
+            // Every built-in (interpreted or compiled-to-WASM) takes the
+            // script/binary path as a plain positional argument, and custom
+            // runtimes registered via `LanguageRegistry` are expected to
+            // follow the same convention.
+            options.args.push(script.to_string());
+        }
+        options
+    }
+}
+
+/// Instantiates and runs a cached [`Runtime`] under Wasmtime, supporting
+/// both core WASI modules and components.
+pub struct Engine {
+    engine: WasmtimeEngine,
+    bytes: Vec<u8>,
+    is_component: bool,
+}
+
+impl Engine {
+    pub fn new(runtime: &Runtime) -> Result<Self> {
+        let bytes = std::fs::read(&runtime.path)?;
+        let inspection = CacheManager::inspect(&bytes)?;
+
+        let mut config = Config::new();
+        config.wasm_component_model(inspection.is_component);
+        let engine =
+            WasmtimeEngine::new(&config).map_err(|e| Error::Other(e.to_string()))?;
+
+        Ok(Self {
+            engine,
+            bytes,
+            is_component: inspection.is_component,
+        })
+    }
+
+    /// Runs the module/component to completion and returns its exit code.
+    pub fn run(&self, options: RunOptions) -> Result<i32> {
+        if self.is_component {
+            self.run_component(options)
+        } else {
+            self.run_module(options)
+        }
+    }
+
+    fn run_module(&self, options: RunOptions) -> Result<i32> {
+        let module =
+            Module::new(&self.engine, &self.bytes).map_err(|e| Error::Other(e.to_string()))?;
+
+        let mut linker: Linker<WasiCtx> = Linker::new(&self.engine);
+        wasmtime_wasi::sync::add_to_linker(&mut linker, |ctx| ctx)
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let wasi_ctx = Self::build_wasi_ctx(&options)?;
+        let mut store = Store::new(&self.engine, wasi_ctx);
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let start = instance
+            .get_typed_func::<(), ()>(&mut store, "_start")
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        match start.call(&mut store, ()) {
+            Ok(()) => Ok(0),
+            Err(trap) => match trap.downcast::<I32Exit>() {
+                Ok(exit) => Ok(exit.0),
+                Err(trap) => Err(Error::Other(trap.to_string())),
+            },
+        }
+    }
+
+    fn run_component(&self, options: RunOptions) -> Result<i32> {
+        let component = Component::new(&self.engine, &self.bytes)
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let mut linker: ComponentLinker<WasiCtx> = ComponentLinker::new(&self.engine);
+        wasmtime_wasi::command::sync::add_to_linker(&mut linker, |ctx| ctx)
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let wasi_ctx = Self::build_wasi_ctx(&options)?;
+        let mut store = Store::new(&self.engine, wasi_ctx);
+
+        let (command, _instance) =
+            wasmtime_wasi::command::sync::Command::instantiate(&mut store, &component, &linker)
+                .map_err(|e| Error::Other(e.to_string()))?;
+
+        match command.wasi_cli_run().call_run(&mut store) {
+            Ok(Ok(())) => Ok(0),
+            Ok(Err(())) => Ok(1),
+            Err(trap) => match trap.downcast::<I32Exit>() {
+                Ok(exit) => Ok(exit.0),
+                Err(trap) => Err(Error::Other(trap.to_string())),
+            },
+        }
+    }
+
+    fn build_wasi_ctx(options: &RunOptions) -> Result<WasiCtx> {
+        let mut builder = WasiCtxBuilder::new();
+        builder.args(&options.args).map_err(|e| Error::Other(e.to_string()))?;
+
+        for (key, value) in &options.env {
+            builder
+                .env(key, value)
+                .map_err(|e| Error::Other(e.to_string()))?;
+        }
+
+        match &options.stdin {
+            Stdio::Inherit => builder.inherit_stdin(),
+            Stdio::Null => &mut builder,
+            Stdio::File(path) => {
+                let file = std::fs::File::open(path)?;
+                builder.stdin(Box::new(wasmtime_wasi::sync::file::File::from_cap_std(
+                    cap_std::fs::File::from_std(file),
+                )))
+            }
+        };
+
+        match &options.stdout {
+            Stdio::Inherit => builder.inherit_stdout(),
+            Stdio::Null => &mut builder,
+            Stdio::File(path) => {
+                let file = std::fs::File::create(path)?;
+                builder.stdout(Box::new(wasmtime_wasi::sync::file::File::from_cap_std(
+                    cap_std::fs::File::from_std(file),
+                )))
+            }
+        };
+
+        match &options.stderr {
+            Stdio::Inherit => builder.inherit_stderr(),
+            Stdio::Null => &mut builder,
+            Stdio::File(path) => {
+                let file = std::fs::File::create(path)?;
+                builder.stderr(Box::new(wasmtime_wasi::sync::file::File::from_cap_std(
+                    cap_std::fs::File::from_std(file),
+                )))
+            }
+        };
+
+        for (host_path, guest_path) in &options.preopen_dirs {
+            let dir = Dir::open_ambient_dir(host_path, cap_std::ambient_authority())?;
+            builder
+                .preopened_dir(dir, guest_path)
+                .map_err(|e| Error::Other(e.to_string()))?;
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_language_prepends_program_name_argv0() {
+        let options = RunOptions::for_language("python", Some("main.py"));
+        assert_eq!(options.args, vec!["python".to_string(), "main.py".to_string()]);
+    }
+
+    #[test]
+    fn test_for_language_without_script_still_sets_argv0() {
+        let options = RunOptions::for_language("python", None);
+        assert_eq!(options.args, vec!["python".to_string()]);
+    }
+}
@@ -3,18 +3,45 @@ use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The library's error type. Every variant carries a plain [`thiserror`]
+/// message; with the optional `diagnostics` feature enabled, it also derives
+/// [`miette::Diagnostic`], attaching a stable error code and actionable
+/// `help` text that a CLI front-end (or any `miette`-aware reporter) can
+/// render for the user.
 #[derive(Error, Debug)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
 pub enum Error {
     #[error("Runtime not found: {language} {version}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::runtime_not_found),
+            help("Call `RuntimeLoader::get_runtime` to download it, or `list_available` to see what's published.")
+        )
+    )]
     RuntimeNotFound { language: String, version: String },
 
     #[error("Network error: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::network),
+            help("Check your network connection. If every source failed, the surrounding error aggregates the URLs that were attempted.")
+        )
+    )]
     Network(#[from] reqwest::Error),
 
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
 
     #[error("Integrity check failed: expected {expected}, got {actual}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::integrity),
+            help("The cached file may be corrupt or the download was tampered with. Clear it with `CacheManager::clear` and re-download.")
+        )
+    )]
     IntegrityCheckFailed { expected: String, actual: String },
 
     #[error("JSON parsing error: {0}")]
@@ -24,11 +51,84 @@ pub enum Error {
     InvalidLanguage(String),
 
     #[error("Manifest not found for {language}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::manifest_not_found),
+            help("Double check the language slug, or register it first via `LanguageRegistry::register`.")
+        )
+    )]
     ManifestNotFound { language: String },
 
     #[error("Version {version} not found for {language}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::version_not_found),
+            help("Call `list_available` to see which versions are published for this language.")
+        )
+    )]
     VersionNotFound { language: String, version: String },
 
+    #[error("No version matches \"{spec}\" for {language}; available versions: {available}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::no_version_matches),
+            help("Loosen the version specifier, or call `list_available` to see what's published.")
+        )
+    )]
+    NoVersionMatches {
+        language: String,
+        spec: String,
+        available: String,
+    },
+
+    #[error("Offline: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::offline),
+            help("Offline mode only serves cached runtimes and manifests. Disable `RuntimeLoaderBuilder::offline` or pre-populate the cache while online.")
+        )
+    )]
+    Offline(String),
+
+    #[error("Language not allowed by configuration: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::language_not_allowed),
+            help("The active `wasmhub.toml` restricts which languages are resolvable via its `languages` filter. Adjust `only`/`except` there, or pass a different `RuntimeLoaderBuilder::language_filter`.")
+        )
+    )]
+    LanguageNotAllowed(String),
+
+    #[error("Invalid WASM module: {0}")]
+    InvalidWasm(String),
+
+    #[error("WASM module does not match declared runtime metadata: {0}")]
+    MetadataMismatch(String),
+
+    /// Every source in `RuntimeLoader`'s source list failed; `attempted`
+    /// names each one tried (a CDN URL, a `Git` remote, or a `Local` path) and
+    /// `errors` holds the corresponding per-source failure, so a
+    /// `diagnostics`-aware reporter can show every attempt instead of just
+    /// the last one.
+    #[error("All sources failed: {}", attempted.join(", "))]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(wasmhub::all_sources_failed),
+            help("Every configured source failed; see the related errors below for the reason each one failed.")
+        )
+    )]
+    AllSourcesFailed {
+        attempted: Vec<String>,
+        #[cfg_attr(feature = "diagnostics", related)]
+        errors: Vec<Error>,
+    },
+
     #[error("{0}")]
     Other(String),
 }
@@ -69,4 +169,19 @@ mod tests {
         let err: Error = "unknown language".to_string().into();
         assert_eq!(err.to_string(), "Invalid language: unknown language");
     }
+
+    #[test]
+    fn test_all_sources_failed_display() {
+        let err = Error::AllSourcesFailed {
+            attempted: vec!["github-releases".to_string(), "jsdelivr".to_string()],
+            errors: vec![
+                Error::Other("timed out".to_string()),
+                Error::Other("404".to_string()),
+            ],
+        };
+        assert_eq!(
+            err.to_string(),
+            "All sources failed: github-releases, jsdelivr"
+        );
+    }
 }
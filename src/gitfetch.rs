@@ -0,0 +1,71 @@
+//! Shallow git clone/checkout helper shared by [`crate::loader`]'s `Git`
+//! source and [`crate::custom`]'s `Git`-sourced custom runtimes, which both
+//! need to fetch a single pinned revision into a scratch directory before
+//! reading (and, for custom runtimes, building) a `.wasm` out of it.
+
+use crate::error::{Error, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A scratch directory holding a shallow checkout of `remote` at `revision`.
+/// Removed (best-effort) on drop, so callers don't have to remember to clean
+/// up after reading/building out of it.
+pub(crate) struct ShallowCheckout {
+    pub(crate) dir: PathBuf,
+    pub(crate) resolved_revision: String,
+}
+
+impl ShallowCheckout {
+    /// Shallow-clones `remote` into a fresh directory under the system temp
+    /// dir (named from `scratch_label`, which callers make unique per
+    /// language/version/revision) and checks out `revision`.
+    pub(crate) fn fetch(scratch_label: &str, remote: &str, revision: &str) -> Result<Self> {
+        let dir = std::env::temp_dir().join(scratch_label);
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        std::fs::create_dir_all(&dir)?;
+
+        let run_git = |args: &[&str]| -> Result<()> {
+            let status = Command::new("git").args(args).current_dir(&dir).status()?;
+            if !status.success() {
+                return Err(Error::Other(format!(
+                    "git {args:?} failed with status {status}"
+                )));
+            }
+            Ok(())
+        };
+
+        run_git(&["init", "-q"])?;
+        run_git(&["remote", "add", "origin", remote])?;
+        run_git(&["fetch", "--depth", "1", "-q", "origin", revision])?;
+        run_git(&["checkout", "-q", "FETCH_HEAD"])?;
+
+        let output = Command::new("git")
+            .args(["rev-parse", "FETCH_HEAD"])
+            .current_dir(&dir)
+            .output()?;
+        if !output.status.success() {
+            return Err(Error::Other(format!(
+                "git rev-parse failed with status {}",
+                output.status
+            )));
+        }
+        let resolved_revision = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        Ok(Self {
+            dir,
+            resolved_revision,
+        })
+    }
+
+    pub(crate) fn join(&self, subpath: &Path) -> PathBuf {
+        self.dir.join(subpath)
+    }
+}
+
+impl Drop for ShallowCheckout {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
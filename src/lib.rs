@@ -9,17 +9,24 @@
 //! - Local caching to avoid redundant downloads
 //! - Support for multiple runtime versions
 //! - Multiple CDN sources with automatic fallback
+//! - Optional `wasmtime` feature to instantiate and run cached runtimes under WASI
+//! - Optional `diagnostics` feature for rich `miette`-powered error reports
+//! - Declarative `wasmhub.toml` config for source ordering and language selection
+//! - Per-project version pinning via a `.wasm-runtime` file
+//! - Lockfile-driven multi-runtime installs via `wasmhub.toml` + `wasmhub.lock`
+//! - Private/bleeding-edge runtimes via `[[custom_runtimes]]`, built from a
+//!   pinned git revision or read from a local path
 //!
 //! ## Example
 //!
 //! ```no_run
-//! use wasm_runtime::{RuntimeLoader, Language};
+//! use wasm_runtime::RuntimeLoader;
 //!
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! let loader = RuntimeLoader::new()?;
 //!
 //! // Download a runtime (or get from cache)
-//! let runtime = loader.get_runtime(Language::Python, "3.11.7").await?;
+//! let runtime = loader.get_runtime("python", "3.11.7").await?;
 //! println!("Runtime path: {:?}", runtime.path);
 //!
 //! // List available runtimes
@@ -29,20 +36,35 @@
 //! }
 //!
 //! // Get latest version for a language
-//! let latest = loader.get_latest_version(Language::Python).await?;
+//! let latest = loader.get_latest_version("python").await?;
 //! println!("Latest Python: {}", latest);
 //! # Ok(())
 //! # }
 //! ```
 
 pub mod cache;
+pub mod config;
+pub mod custom;
+#[cfg(feature = "wasmtime")]
+pub mod engine;
 pub mod error;
+pub(crate) mod gitfetch;
 pub mod loader;
+pub mod lock;
 pub mod manifest;
+pub mod pin;
+pub mod registry;
 pub mod runtime;
 
-pub use cache::CacheManager;
+pub use cache::{CacheManager, CacheUsage, RuntimeProvenance, WasmInspection};
+pub use config::{LanguageFilter, WasmhubConfig};
+pub use custom::{CustomRuntime, CustomRuntimeSource};
+#[cfg(feature = "wasmtime")]
+pub use engine::{Engine, RunOptions, Stdio};
 pub use error::{Error, Result};
-pub use loader::{CdnSource, RuntimeLoader, RuntimeLoaderBuilder};
+pub use loader::{RuntimeLoader, RuntimeLoaderBuilder, RuntimeSource};
+pub use lock::{LockFile, LockedRuntime};
 pub use manifest::{GlobalManifest, RuntimeInfo, RuntimeManifest, RuntimeVersion};
+pub use pin::PinFile;
+pub use registry::{LanguageDescriptor, LanguageRegistry};
 pub use runtime::{Language, Runtime};
@@ -1,9 +1,16 @@
-use crate::cache::CacheManager;
+use crate::cache::{CacheManager, RuntimeProvenance};
+use crate::config::{LanguageFilter, WasmhubConfig};
+use crate::custom::CustomRuntime;
 use crate::error::{Error, Result};
+use crate::gitfetch::ShallowCheckout;
 use crate::manifest::{GlobalManifest, RuntimeManifest};
-use crate::runtime::{Language, Runtime};
+use crate::registry::LanguageRegistry;
+use crate::runtime::Runtime;
 use reqwest::Client;
-use std::path::PathBuf;
+use semver::VersionReq;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 #[cfg(feature = "progress")]
 use futures_util::StreamExt;
@@ -11,17 +18,58 @@ use futures_util::StreamExt;
 const GITHUB_RELEASES_BASE: &str = "https://github.com/anistark/wasm-runtime/releases/download";
 const JSDELIVR_BASE: &str = "https://cdn.jsdelivr.net/gh/anistark/wasm-runtime@latest";
 
-#[derive(Debug, Clone)]
-pub enum CdnSource {
+/// Where a runtime's bytes (and, for the CDN variants, its manifest) can be
+/// fetched from. `Git`/`Local` let a user pin a runtime to an exact commit in
+/// any repo or point at a `.wasm` already on disk, the same way a grammar
+/// loader might declare either a local path or a git remote with an exact
+/// revision and optional subpath.
+///
+/// `Serialize`/`Deserialize` let a source list round-trip through a
+/// `wasmhub.toml`, e.g. `type = "github-releases"` or a `[[sources]]` table
+/// with `type = "git"`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RuntimeSource {
+    #[serde(rename = "github-releases")]
     GitHubReleases,
+    #[serde(rename = "jsdelivr")]
     JsDelivr,
+    /// A specific commit in a git remote. Shallow-cloned into a scratch
+    /// directory, checked out at `revision`, then `subpath` (or
+    /// `<language>-<version>.wasm` at the repo root if unset) is read.
+    Git {
+        remote: String,
+        revision: String,
+        subpath: Option<PathBuf>,
+    },
+    /// A `.wasm` file already on disk. Skips the network and, since there's
+    /// no CDN-published hash to check it against, the SHA256 integrity
+    /// check.
+    Local { path: PathBuf },
 }
 
-impl CdnSource {
-    fn base_url(&self) -> &'static str {
+impl RuntimeSource {
+    /// The base URL for manifest/version-list lookups. Only the CDN
+    /// variants serve a `manifest.json`; `Git`/`Local` sources are
+    /// download-only and have no base URL.
+    pub fn base_url(&self) -> Option<&str> {
         match self {
-            CdnSource::GitHubReleases => GITHUB_RELEASES_BASE,
-            CdnSource::JsDelivr => JSDELIVR_BASE,
+            RuntimeSource::GitHubReleases => Some(GITHUB_RELEASES_BASE),
+            RuntimeSource::JsDelivr => Some(JSDELIVR_BASE),
+            RuntimeSource::Git { .. } | RuntimeSource::Local { .. } => None,
+        }
+    }
+
+    /// A short label identifying this source, e.g. for a `Git`/`Local` entry
+    /// in `Error::AllSourcesFailed`'s `attempted` list (a CDN entry is listed
+    /// by its download URL) or the `doctor` subcommand's source reachability
+    /// report.
+    pub fn describe(&self) -> String {
+        match self {
+            RuntimeSource::GitHubReleases => "github-releases".to_string(),
+            RuntimeSource::JsDelivr => "jsdelivr".to_string(),
+            RuntimeSource::Git { remote, revision, .. } => format!("git:{remote}@{revision}"),
+            RuntimeSource::Local { path } => format!("local:{}", path.display()),
         }
     }
 }
@@ -29,7 +77,20 @@ impl CdnSource {
 pub struct RuntimeLoader {
     cache: CacheManager,
     client: Client,
-    cdn_sources: Vec<CdnSource>,
+    sources: Vec<RuntimeSource>,
+    registry: LanguageRegistry,
+    language_filter: Option<LanguageFilter>,
+    /// How long a disk-cached manifest is trusted before it's revalidated
+    /// against upstream. Zero (the default) means every manifest fetch
+    /// revalidates.
+    manifest_ttl: Duration,
+    /// When set, `get_runtime`/`get_runtime_matching` serve only from
+    /// cache and never attempt a CDN request.
+    offline: bool,
+    /// Private/bleeding-edge runtimes declared via `wasmhub.toml`'s
+    /// `[[custom_runtimes]]`, installed on demand by
+    /// [`RuntimeLoader::install_custom_runtimes`].
+    custom_runtimes: Vec<CustomRuntime>,
     #[cfg(feature = "progress")]
     show_progress: bool,
 }
@@ -39,7 +100,12 @@ impl RuntimeLoader {
         Ok(Self {
             cache: CacheManager::new()?,
             client: Client::new(),
-            cdn_sources: vec![CdnSource::GitHubReleases, CdnSource::JsDelivr],
+            sources: vec![RuntimeSource::GitHubReleases, RuntimeSource::JsDelivr],
+            registry: LanguageRegistry::with_builtins(),
+            language_filter: None,
+            manifest_ttl: Duration::ZERO,
+            offline: false,
+            custom_runtimes: Vec::new(),
             #[cfg(feature = "progress")]
             show_progress: false,
         })
@@ -49,67 +115,268 @@ impl RuntimeLoader {
         RuntimeLoaderBuilder::default()
     }
 
-    pub async fn get_runtime(&self, language: Language, version: &str) -> Result<Runtime> {
-        if let Some(runtime) = self.cache.get(language, version) {
+    /// Resolves `language` (a slug or a registered alias) to its canonical
+    /// slug via the loader's [`LanguageRegistry`], falling back to the
+    /// input unchanged if it isn't registered.
+    fn canonical_language(&self, language: &str) -> String {
+        self.registry
+            .resolve(language)
+            .unwrap_or(language)
+            .to_string()
+    }
+
+    /// Checks `language` (already canonicalized) against the configured
+    /// [`LanguageFilter`], if any.
+    fn check_language_allowed(&self, language: &str) -> Result<()> {
+        match &self.language_filter {
+            Some(filter) if !filter.allows(language) => {
+                Err(Error::LanguageNotAllowed(language.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn get_runtime(&self, language: &str, version: &str) -> Result<Runtime> {
+        let language = self.canonical_language(language);
+        self.check_language_allowed(&language)?;
+
+        if let Some(runtime) = self.cache.get(&language, version) {
+            return Ok(runtime);
+        }
+
+        let resolved = self.resolve_version(&language, version).await?;
+        if let Some(runtime) = self.cache.get(&language, &resolved) {
             return Ok(runtime);
         }
 
-        self.download_runtime(language, version).await
+        self.download_runtime(&language, &resolved).await
     }
 
-    pub async fn download_runtime(&self, language: Language, version: &str) -> Result<Runtime> {
-        let manifest = self.fetch_runtime_manifest(language).await?;
-        let version_info = manifest
-            .get_version(version)
-            .ok_or_else(|| Error::VersionNotFound {
-                language: language.to_string(),
-                version: version.to_string(),
+    /// Like [`RuntimeLoader::get_runtime`], but resolves to the highest
+    /// published version satisfying a `semver::VersionReq` (e.g.
+    /// `">=3.11, <3.12"` or `"^3.11"`) rather than a single loose specifier,
+    /// using [`RuntimeManifest::resolve_requirement`] for the comparison,
+    /// the same comparator [`RuntimeLoader::get_latest_version_matching`]
+    /// uses. A plain `"latest"` is not compared this way: it is the
+    /// manifest's declared `latest` entry.
+    pub async fn get_runtime_matching(&self, language: &str, req: &VersionReq) -> Result<Runtime> {
+        let language = self.canonical_language(language);
+        self.check_language_allowed(&language)?;
+        let manifest = self.fetch_runtime_manifest(&language).await?;
+        let (version, _) =
+            manifest
+                .resolve_requirement(req)
+                .ok_or_else(|| Error::NoVersionMatches {
+                    language: language.clone(),
+                    spec: req.to_string(),
+                    available: manifest.versions.keys().cloned().collect::<Vec<_>>().join(", "),
+                })?;
+        let version = version.to_string();
+
+        if let Some(runtime) = self.cache.get(&language, &version) {
+            return Ok(runtime);
+        }
+
+        self.download_runtime(&language, &version).await
+    }
+
+    /// Resolves a loose version specifier (`"latest"`, `"lts"`, a prefix like
+    /// `"3.11"`, or a range like `">=3.10,<3.12"`) to a concrete version
+    /// string, preferring cached matches over remote-only ones.
+    pub async fn resolve_version(&self, language: &str, spec: &str) -> Result<String> {
+        let language = self.canonical_language(language);
+        let manifest = self.fetch_global_manifest().await?;
+        let info = manifest
+            .get_language(&language)
+            .ok_or_else(|| Error::ManifestNotFound {
+                language: language.clone(),
             })?;
 
-        let mut last_error = None;
-        for source in &self.cdn_sources {
-            let url = self.build_download_url(source, language, version);
-            match self.download_from_url(&url).await {
+        let cached_versions: Vec<String> = self
+            .cache
+            .list()?
+            .into_iter()
+            .filter(|runtime| runtime.language == language)
+            .map(|runtime| runtime.version)
+            .collect();
+
+        info.resolve_version(&language, spec, &cached_versions)
+    }
+
+    pub async fn download_runtime(&self, language: &str, version: &str) -> Result<Runtime> {
+        let language = self.canonical_language(language);
+
+        let has_non_cdn_source = self
+            .sources
+            .iter()
+            .any(|source| !matches!(source, RuntimeSource::GitHubReleases | RuntimeSource::JsDelivr));
+
+        // A `Local` source (and a `Git` source's own fetch attempt, which
+        // fails on its own if it truly needs the network) doesn't go
+        // through a CDN, so offline mode should only hard-block a request
+        // that has no choice but to hit a CDN.
+        if self.offline && !has_non_cdn_source {
+            return Err(Error::Offline(format!(
+                "{language} {version} is not cached and offline mode is enabled"
+            )));
+        }
+
+        // `Local`/`Git` sources carry their own path or pinned revision and
+        // don't need a central manifest entry to resolve "language x
+        // version"; only a CDN source (whose download URL and
+        // CDN-published hash come from the manifest) does. So a manifest
+        // fetch failure is fatal only when every configured source is a
+        // CDN with nothing else to fall back on.
+        let manifest = match self.fetch_runtime_manifest(&language).await {
+            Ok(manifest) => Some(manifest),
+            Err(e) if has_non_cdn_source => {
+                let _ = e;
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        let version_info = manifest.as_ref().and_then(|manifest| manifest.get_version(version));
+
+        if version_info.is_none() && !has_non_cdn_source {
+            return Err(Error::VersionNotFound {
+                language: language.clone(),
+                version: version.to_string(),
+            });
+        }
+
+        let mut attempted = Vec::new();
+        let mut errors = Vec::new();
+        for source in &self.sources {
+            let is_cdn = matches!(source, RuntimeSource::GitHubReleases | RuntimeSource::JsDelivr);
+            attempted.push(if is_cdn {
+                self.build_download_url(source, &language, version)
+            } else {
+                source.describe()
+            });
+
+            // Offline mode never hits a CDN, even when a `Local`/`Git`
+            // source earlier in the list failed.
+            if self.offline && is_cdn {
+                errors.push(Error::Offline(format!(
+                    "skipped {} while offline",
+                    source.describe()
+                )));
+                continue;
+            }
+
+            // A CDN source still needs the manifest entry to know what to
+            // verify against; skip it (rather than fetch unverifiable
+            // bytes) when none was available.
+            if version_info.is_none()
+                && matches!(source, RuntimeSource::GitHubReleases | RuntimeSource::JsDelivr)
+            {
+                errors.push(Error::VersionNotFound {
+                    language: language.clone(),
+                    version: version.to_string(),
+                });
+                continue;
+            }
+
+            match self.fetch_from_source(source, &language, version).await {
                 Ok(data) => {
-                    let computed_hash = self.compute_hash(&data);
-                    if computed_hash != version_info.sha256 {
-                        return Err(Error::IntegrityCheckFailed {
-                            expected: version_info.sha256.clone(),
-                            actual: computed_hash,
-                        });
+                    // A `Local` source has no CDN-published hash to check
+                    // the bytes against; neither does a `Git`/CDN source
+                    // when no manifest entry was available to check
+                    // against. Every other combination is verified.
+                    if !matches!(source, RuntimeSource::Local { .. }) {
+                        if let Some(version_info) = version_info {
+                            let computed_hash = self.compute_hash(&data);
+                            if computed_hash != version_info.sha256 {
+                                return Err(Error::IntegrityCheckFailed {
+                                    expected: version_info.sha256.clone(),
+                                    actual: computed_hash,
+                                });
+                            }
+                        }
                     }
 
-                    return self.cache.store(language, version, &data);
+                    return match version_info {
+                        Some(version_info) => {
+                            self.cache.store_verified(&language, version, &data, version_info)
+                        }
+                        None => self.cache.store(&language, version, &data),
+                    };
                 }
                 Err(e) => {
-                    last_error = Some(e);
+                    errors.push(e);
                     continue;
                 }
             }
         }
 
-        Err(last_error.unwrap_or_else(|| Error::Other("All CDN sources failed".to_string())))
+        Err(Error::AllSourcesFailed { attempted, errors })
+    }
+
+    async fn fetch_from_source(
+        &self,
+        source: &RuntimeSource,
+        language: &str,
+        version: &str,
+    ) -> Result<Vec<u8>> {
+        match source {
+            RuntimeSource::GitHubReleases | RuntimeSource::JsDelivr => {
+                let url = self.build_download_url(source, language, version);
+                self.download_from_url(&url).await
+            }
+            RuntimeSource::Git {
+                remote,
+                revision,
+                subpath,
+            } => Self::fetch_from_git(remote, revision, subpath.as_deref(), language, version),
+            RuntimeSource::Local { path } => std::fs::read(path).map_err(Error::from),
+        }
+    }
+
+    /// Shallow-clones `remote` into a scratch directory, checks out
+    /// `revision`, and reads `subpath` (or `<language>-<version>.wasm` at
+    /// the repo root if `subpath` is unset).
+    fn fetch_from_git(
+        remote: &str,
+        revision: &str,
+        subpath: Option<&Path>,
+        language: &str,
+        version: &str,
+    ) -> Result<Vec<u8>> {
+        let checkout = ShallowCheckout::fetch(
+            &format!("wasm-runtime-git-{language}-{version}-{revision}"),
+            remote,
+            revision,
+        )?;
+
+        let file_path = match subpath {
+            Some(subpath) => checkout.join(subpath),
+            None => checkout.join(Path::new(&format!("{language}-{version}.wasm"))),
+        };
+        std::fs::read(&file_path).map_err(Error::from)
     }
 
-    fn build_download_url(&self, source: &CdnSource, language: Language, version: &str) -> String {
+    fn build_download_url(&self, source: &RuntimeSource, language: &str, version: &str) -> String {
         match source {
-            CdnSource::GitHubReleases => {
+            RuntimeSource::GitHubReleases => {
                 format!(
                     "{}/v{}/{}-{}.wasm",
-                    source.base_url(),
+                    source.base_url().expect("CDN source has a base URL"),
                     version,
-                    language.as_str(),
+                    language,
                     version
                 )
             }
-            CdnSource::JsDelivr => {
+            RuntimeSource::JsDelivr => {
                 format!(
                     "{}/runtimes/{}/{}.wasm",
-                    source.base_url(),
-                    language.as_str(),
+                    source.base_url().expect("CDN source has a base URL"),
+                    language,
                     version
                 )
             }
+            RuntimeSource::Git { .. } | RuntimeSource::Local { .. } => {
+                unreachable!("build_download_url is only called for CDN sources")
+            }
         }
     }
 
@@ -170,22 +437,51 @@ impl RuntimeLoader {
     }
 
     pub async fn list_available(&self) -> Result<GlobalManifest> {
-        self.fetch_global_manifest().await
+        let mut manifest = self.fetch_global_manifest().await?;
+        if let Some(filter) = &self.language_filter {
+            manifest.languages.retain(|language, _| filter.allows(language));
+        }
+        Ok(manifest)
     }
 
-    pub async fn get_latest_version(&self, language: Language) -> Result<String> {
+    pub async fn get_latest_version(&self, language: &str) -> Result<String> {
+        let language = self.canonical_language(language);
+        self.check_language_allowed(&language)?;
         let manifest = self.fetch_global_manifest().await?;
-        let runtime_info =
+        let runtime_info = manifest
+            .get_language(&language)
+            .ok_or_else(|| Error::ManifestNotFound {
+                language: language.clone(),
+            })?;
+        Ok(runtime_info.latest.clone())
+    }
+
+    /// Like [`RuntimeLoader::get_latest_version`], but resolves to the
+    /// highest published version satisfying a `semver::VersionReq` rather
+    /// than the manifest's unconstrained `latest`, reusing
+    /// [`RuntimeManifest::resolve_requirement`] (the same comparator
+    /// [`RuntimeLoader::get_runtime_matching`] uses) so both agree on the
+    /// newest version within a range. The unconstrained
+    /// [`RuntimeLoader::get_latest_version`] does not compare versions; it
+    /// returns the manifest's declared `latest` as is.
+    pub async fn get_latest_version_matching(&self, language: &str, req: &VersionReq) -> Result<String> {
+        let language = self.canonical_language(language);
+        self.check_language_allowed(&language)?;
+        let manifest = self.fetch_runtime_manifest(&language).await?;
+        let (version, _) =
             manifest
-                .get_language(language.as_str())
-                .ok_or_else(|| Error::ManifestNotFound {
-                    language: language.to_string(),
+                .resolve_requirement(req)
+                .ok_or_else(|| Error::NoVersionMatches {
+                    language: language.clone(),
+                    spec: req.to_string(),
+                    available: manifest.versions.keys().cloned().collect::<Vec<_>>().join(", "),
                 })?;
-        Ok(runtime_info.latest.clone())
+        Ok(version.to_string())
     }
 
-    pub fn clear_cache(&self, language: Language, version: &str) -> Result<()> {
-        self.cache.clear(language, version)
+    pub fn clear_cache(&self, language: &str, version: &str) -> Result<()> {
+        let language = self.canonical_language(language);
+        self.cache.clear(&language, version)
     }
 
     pub fn clear_all_cache(&self) -> Result<()> {
@@ -196,71 +492,230 @@ impl RuntimeLoader {
         self.cache.list()
     }
 
+    /// The ordered list of sources `download_runtime` tries, for callers
+    /// (e.g. the `doctor` subcommand) that want to report on each one
+    /// without re-deriving it from config.
+    pub fn sources(&self) -> &[RuntimeSource] {
+        &self.sources
+    }
+
+    /// Whether `source`'s manifest endpoint responds successfully. Only
+    /// meaningful for the CDN variants (`base_url` is `Some`); `Git`/`Local`
+    /// sources have nothing to probe and are always reported reachable.
+    pub async fn check_source_reachability(&self, source: &RuntimeSource) -> bool {
+        let Some(base_url) = source.base_url() else {
+            return true;
+        };
+        self.client
+            .head(base_url)
+            .send()
+            .await
+            .map(|response| response.status().is_success() || response.status().is_redirection())
+            .unwrap_or(false)
+    }
+
+    /// Resolves (cloning/building or reading from disk, per
+    /// [`CustomRuntime::resolve`]) and caches every `[[custom_runtimes]]`
+    /// entry from `wasmhub.toml`, registering each under its declared
+    /// `language`/`version` so `get_runtime` (and with it `Get` and `Run`)
+    /// serves it from the cache like a first-party runtime. `Info` reads
+    /// only the central manifest and does not list these entries. The
+    /// resolved git revision (if any) and computed sha256 are recorded via
+    /// [`CacheManager::store_provenance`] for `CacheAction::Show` to report.
+    /// Every entry is resolved (fetched, and built if configured) on each
+    /// call; only the cache write is skipped when the cached sha256 already
+    /// matches what `resolve` produced.
+    pub async fn install_custom_runtimes(&self) -> Result<Vec<Runtime>> {
+        let mut installed = Vec::new();
+        for entry in &self.custom_runtimes {
+            let (data, revision) = entry.resolve()?;
+            let sha256 = {
+                use sha2::{Digest, Sha256};
+                let mut hasher = Sha256::new();
+                hasher.update(&data);
+                format!("{:x}", hasher.finalize())
+            };
+
+            let runtime = match self.cache.get(&entry.language, &entry.version) {
+                Some(runtime) if runtime.sha256 == sha256 => runtime,
+                _ => self.cache.store(&entry.language, &entry.version, &data)?,
+            };
+
+            self.cache.store_provenance(
+                &entry.language,
+                &entry.version,
+                &RuntimeProvenance {
+                    source: entry.describe_source(),
+                    revision,
+                    sha256,
+                },
+            )?;
+
+            installed.push(runtime);
+        }
+        Ok(installed)
+    }
+
+    /// Fetches the global manifest, preferring a fresh disk-cached copy
+    /// (one younger than `manifest_ttl`) over the network, falling back to
+    /// a stale cached copy if every source fails, and in `offline` mode
+    /// never touching the network at all.
     async fn fetch_global_manifest(&self) -> Result<GlobalManifest> {
-        let mut last_error = None;
-        for source in &self.cdn_sources {
-            let url = match source {
-                CdnSource::GitHubReleases => {
-                    format!("{}/latest/manifest.json", source.base_url())
-                }
-                CdnSource::JsDelivr => {
-                    format!("{}/manifest.json", source.base_url())
+        const SCOPE: &str = "global";
+
+        if self.offline {
+            let (raw, _) = self.cache.get_cached_manifest(SCOPE).ok_or_else(|| {
+                Error::Offline("no cached manifest available while offline".to_string())
+            })?;
+            return serde_json::from_str(&raw).map_err(Error::from);
+        }
+
+        if let Some((raw, fetched_at)) = self.cache.get_cached_manifest(SCOPE) {
+            if Self::is_fresh(fetched_at, self.manifest_ttl) {
+                if let Ok(manifest) = serde_json::from_str(&raw) {
+                    return Ok(manifest);
                 }
+            }
+        }
+
+        match self.fetch_global_manifest_remote().await {
+            Ok((manifest, raw)) => {
+                let _ = self.cache.store_cached_manifest(SCOPE, &raw);
+                Ok(manifest)
+            }
+            Err(e) => self
+                .cache
+                .get_cached_manifest(SCOPE)
+                .and_then(|(raw, _)| serde_json::from_str(&raw).ok())
+                .ok_or(e),
+        }
+    }
+
+    async fn fetch_global_manifest_remote(&self) -> Result<(GlobalManifest, String)> {
+        let mut attempted = Vec::new();
+        let mut errors = Vec::new();
+        for source in &self.sources {
+            let Some(base_url) = source.base_url() else {
+                // Git/Local sources don't serve a manifest.
+                continue;
+            };
+            let url = match source {
+                RuntimeSource::GitHubReleases => format!("{base_url}/latest/manifest.json"),
+                RuntimeSource::JsDelivr => format!("{base_url}/manifest.json"),
+                RuntimeSource::Git { .. } | RuntimeSource::Local { .. } => unreachable!(),
             };
 
-            match self.fetch_json(&url).await {
-                Ok(manifest) => return Ok(manifest),
+            attempted.push(url.clone());
+            match self.fetch_text(&url).await {
+                Ok(text) => match serde_json::from_str(&text) {
+                    Ok(manifest) => return Ok((manifest, text)),
+                    Err(e) => {
+                        errors.push(Error::from(e));
+                        continue;
+                    }
+                },
                 Err(e) => {
-                    last_error = Some(e);
+                    errors.push(e);
                     continue;
                 }
             }
         }
 
-        Err(last_error.unwrap_or_else(|| Error::Other("Failed to fetch manifest".to_string())))
+        if attempted.is_empty() {
+            return Err(Error::Other(
+                "No CDN sources configured to fetch a manifest from".to_string(),
+            ));
+        }
+        Err(Error::AllSourcesFailed { attempted, errors })
+    }
+
+    /// Fetches a per-language runtime manifest with the same cache/TTL/
+    /// offline behavior as [`RuntimeLoader::fetch_global_manifest`], keyed
+    /// by the language's canonical slug.
+    pub async fn fetch_runtime_manifest(&self, language: &str) -> Result<RuntimeManifest> {
+        if self.offline {
+            let (raw, _) = self.cache.get_cached_manifest(language).ok_or_else(|| {
+                Error::Offline(format!(
+                    "no cached manifest available for {language} while offline"
+                ))
+            })?;
+            return serde_json::from_str(&raw).map_err(Error::from);
+        }
+
+        if let Some((raw, fetched_at)) = self.cache.get_cached_manifest(language) {
+            if Self::is_fresh(fetched_at, self.manifest_ttl) {
+                if let Ok(manifest) = serde_json::from_str(&raw) {
+                    return Ok(manifest);
+                }
+            }
+        }
+
+        match self.fetch_runtime_manifest_remote(language).await {
+            Ok((manifest, raw)) => {
+                let _ = self.cache.store_cached_manifest(language, &raw);
+                Ok(manifest)
+            }
+            Err(e) => self
+                .cache
+                .get_cached_manifest(language)
+                .and_then(|(raw, _)| serde_json::from_str(&raw).ok())
+                .ok_or(e),
+        }
     }
 
-    async fn fetch_runtime_manifest(&self, language: Language) -> Result<RuntimeManifest> {
-        let mut last_error = None;
-        for source in &self.cdn_sources {
+    async fn fetch_runtime_manifest_remote(&self, language: &str) -> Result<(RuntimeManifest, String)> {
+        let mut attempted = Vec::new();
+        let mut errors = Vec::new();
+        for source in &self.sources {
+            let Some(base_url) = source.base_url() else {
+                // Git/Local sources don't serve a manifest.
+                continue;
+            };
             let url = match source {
-                CdnSource::GitHubReleases => {
-                    format!(
-                        "{}/latest/runtimes/{}/manifest.json",
-                        source.base_url(),
-                        language.as_str()
-                    )
+                RuntimeSource::GitHubReleases => {
+                    format!("{base_url}/latest/runtimes/{language}/manifest.json")
                 }
-                CdnSource::JsDelivr => {
-                    format!(
-                        "{}/runtimes/{}/manifest.json",
-                        source.base_url(),
-                        language.as_str()
-                    )
+                RuntimeSource::JsDelivr => {
+                    format!("{base_url}/runtimes/{language}/manifest.json")
                 }
+                RuntimeSource::Git { .. } | RuntimeSource::Local { .. } => unreachable!(),
             };
 
-            match self.fetch_json(&url).await {
-                Ok(manifest) => return Ok(manifest),
+            attempted.push(url.clone());
+            match self.fetch_text(&url).await {
+                Ok(text) => match serde_json::from_str(&text) {
+                    Ok(manifest) => return Ok((manifest, text)),
+                    Err(e) => {
+                        errors.push(Error::from(e));
+                        continue;
+                    }
+                },
                 Err(e) => {
-                    last_error = Some(e);
+                    errors.push(e);
                     continue;
                 }
             }
         }
 
-        Err(last_error.unwrap_or_else(|| Error::ManifestNotFound {
-            language: language.to_string(),
-        }))
+        if attempted.is_empty() {
+            return Err(Error::ManifestNotFound {
+                language: language.to_string(),
+            });
+        }
+        Err(Error::AllSourcesFailed { attempted, errors })
+    }
+
+    /// Whether a manifest fetched at `fetched_at` is still within `ttl`.
+    fn is_fresh(fetched_at: SystemTime, ttl: Duration) -> bool {
+        fetched_at.elapsed().map(|age| age < ttl).unwrap_or(false)
     }
 
-    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+    async fn fetch_text(&self, url: &str) -> Result<String> {
         let response = self.client.get(url).send().await?;
         if !response.status().is_success() {
             return Err(Error::Network(response.error_for_status().unwrap_err()));
         }
-        let json = response.json().await?;
-        Ok(json)
+        Ok(response.text().await?)
     }
 }
 
@@ -273,7 +728,13 @@ impl Default for RuntimeLoader {
 #[derive(Default)]
 pub struct RuntimeLoaderBuilder {
     cache_dir: Option<PathBuf>,
-    cdn_sources: Option<Vec<CdnSource>>,
+    sources: Option<Vec<RuntimeSource>>,
+    registry: Option<LanguageRegistry>,
+    language_filter: Option<LanguageFilter>,
+    config_path: Option<PathBuf>,
+    manifest_ttl: Option<Duration>,
+    offline: bool,
+    custom_runtimes: Option<Vec<CustomRuntime>>,
     #[cfg(feature = "progress")]
     show_progress: bool,
 }
@@ -288,8 +749,57 @@ impl RuntimeLoaderBuilder {
         self
     }
 
-    pub fn cdn_sources(mut self, sources: Vec<CdnSource>) -> Self {
-        self.cdn_sources = Some(sources);
+    /// Sets the ordered list of sources `download_runtime` tries, e.g. to
+    /// prepend a pinned [`RuntimeSource::Git`] or [`RuntimeSource::Local`]
+    /// ahead of the default CDNs.
+    pub fn sources(mut self, sources: Vec<RuntimeSource>) -> Self {
+        self.sources = Some(sources);
+        self
+    }
+
+    /// Overrides the default (built-ins-only) [`LanguageRegistry`], e.g.
+    /// with one loaded from a `GlobalManifest` or user config file.
+    pub fn language_registry(mut self, registry: LanguageRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Restricts which languages `get_runtime`, `get_runtime_matching`,
+    /// `get_latest_version`, and `list_available` will resolve.
+    pub fn language_filter(mut self, filter: LanguageFilter) -> Self {
+        self.language_filter = Some(filter);
+        self
+    }
+
+    /// Loads a `wasmhub.toml` from an explicit path instead of the default
+    /// (current-directory) location. Unlike the default location, a missing
+    /// file at an explicit path is an error.
+    pub fn config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// How long a disk-cached manifest is trusted before it's revalidated
+    /// against upstream (the source of truth for a runtime's `latest`
+    /// pointer and `sha256`). Defaults to zero, i.e. always revalidate.
+    pub fn manifest_ttl(mut self, ttl: Duration) -> Self {
+        self.manifest_ttl = Some(ttl);
+        self
+    }
+
+    /// When `true`, `get_runtime`/`get_runtime_matching`/`download_runtime`
+    /// serve only from the local cache and fail with [`Error::Offline`]
+    /// instead of making any CDN request.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Overrides the custom runtimes installed by
+    /// [`RuntimeLoader::install_custom_runtimes`] instead of whatever
+    /// `wasmhub.toml`'s `[[custom_runtimes]]` declares.
+    pub fn custom_runtimes(mut self, custom_runtimes: Vec<CustomRuntime>) -> Self {
+        self.custom_runtimes = Some(custom_runtimes);
         self
     }
 
@@ -300,18 +810,36 @@ impl RuntimeLoaderBuilder {
     }
 
     pub fn build(self) -> Result<RuntimeLoader> {
-        let cache = if let Some(cache_dir) = self.cache_dir {
+        let config = match &self.config_path {
+            Some(path) => WasmhubConfig::load(path)?,
+            None => WasmhubConfig::load_default()?,
+        };
+
+        let cache_dir = self.cache_dir.or(config.cache_dir);
+        let cache = if let Some(cache_dir) = cache_dir {
             CacheManager::with_cache_dir(cache_dir)
         } else {
             CacheManager::new()?
         };
 
+        let sources = self.sources.or(config.sources).unwrap_or_else(|| {
+            vec![RuntimeSource::GitHubReleases, RuntimeSource::JsDelivr]
+        });
+        let language_filter = self.language_filter.or(config.languages);
+        let custom_runtimes = self
+            .custom_runtimes
+            .or(config.custom_runtimes)
+            .unwrap_or_default();
+
         Ok(RuntimeLoader {
             cache,
             client: Client::new(),
-            cdn_sources: self
-                .cdn_sources
-                .unwrap_or_else(|| vec![CdnSource::GitHubReleases, CdnSource::JsDelivr]),
+            sources,
+            registry: self.registry.unwrap_or_default(),
+            language_filter,
+            manifest_ttl: self.manifest_ttl.unwrap_or(Duration::ZERO),
+            offline: self.offline,
+            custom_runtimes,
             #[cfg(feature = "progress")]
             show_progress: self.show_progress,
         })
@@ -325,24 +853,38 @@ mod tests {
     #[test]
     fn test_cdn_source_base_url() {
         assert_eq!(
-            CdnSource::GitHubReleases.base_url(),
-            "https://github.com/anistark/wasm-runtime/releases/download"
+            RuntimeSource::GitHubReleases.base_url(),
+            Some("https://github.com/anistark/wasm-runtime/releases/download")
         );
         assert_eq!(
-            CdnSource::JsDelivr.base_url(),
-            "https://cdn.jsdelivr.net/gh/anistark/wasm-runtime@latest"
+            RuntimeSource::JsDelivr.base_url(),
+            Some("https://cdn.jsdelivr.net/gh/anistark/wasm-runtime@latest")
         );
     }
 
+    #[test]
+    fn test_git_and_local_sources_have_no_base_url() {
+        let git = RuntimeSource::Git {
+            remote: "https://example.com/repo.git".to_string(),
+            revision: "abc123".to_string(),
+            subpath: None,
+        };
+        let local = RuntimeSource::Local {
+            path: PathBuf::from("/tmp/python-3.11.7.wasm"),
+        };
+        assert_eq!(git.base_url(), None);
+        assert_eq!(local.base_url(), None);
+    }
+
     #[test]
     fn test_build_download_url() {
         let loader = RuntimeLoader::new().unwrap();
 
-        let url = loader.build_download_url(&CdnSource::GitHubReleases, Language::Python, "3.11.7");
+        let url = loader.build_download_url(&RuntimeSource::GitHubReleases, "python", "3.11.7");
         assert!(url.contains("releases/download"));
         assert!(url.contains("python-3.11.7.wasm"));
 
-        let url = loader.build_download_url(&CdnSource::JsDelivr, Language::Python, "3.11.7");
+        let url = loader.build_download_url(&RuntimeSource::JsDelivr, "python", "3.11.7");
         assert!(url.contains("cdn.jsdelivr.net"));
         assert!(url.contains("runtimes/python/3.11.7.wasm"));
     }
@@ -358,11 +900,86 @@ mod tests {
     #[test]
     fn test_builder() {
         let loader = RuntimeLoader::builder()
-            .cdn_sources(vec![CdnSource::GitHubReleases])
+            .sources(vec![RuntimeSource::GitHubReleases])
+            .build()
+            .unwrap();
+
+        assert_eq!(loader.sources.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_from_local_source_skips_network() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let wasm_path = temp_dir.path().join("python-3.11.7.wasm");
+        std::fs::write(&wasm_path, b"local wasm bytes").unwrap();
+
+        let loader = RuntimeLoader::new().unwrap();
+        let source = RuntimeSource::Local {
+            path: wasm_path.clone(),
+        };
+
+        let data = loader
+            .fetch_from_source(&source, "python", "3.11.7")
+            .await
+            .expect("local source should be read from disk");
+        assert_eq!(data, b"local wasm bytes");
+    }
+
+    #[tokio::test]
+    async fn test_download_runtime_from_local_only_source_skips_manifest() {
+        use tempfile::TempDir;
+
+        let wasm_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let wasm_path = wasm_dir.path().join("python-3.11.7.wasm");
+        std::fs::write(&wasm_path, b"local wasm bytes").unwrap();
+
+        // No CDN in the source list, so there is no manifest to fetch and
+        // no network call should ever be attempted.
+        let loader = RuntimeLoader::builder()
+            .cache_dir(cache_dir.path().to_path_buf())
+            .sources(vec![RuntimeSource::Local {
+                path: wasm_path.clone(),
+            }])
+            .build()
+            .unwrap();
+
+        let runtime = loader
+            .download_runtime("python", "3.11.7")
+            .await
+            .expect("a Local-only source list should resolve without a manifest entry");
+        assert_eq!(runtime.version, "3.11.7");
+        assert_eq!(std::fs::read(&runtime.path).unwrap(), b"local wasm bytes");
+    }
+
+    #[tokio::test]
+    async fn test_download_runtime_offline_still_serves_local_source() {
+        use tempfile::TempDir;
+
+        let wasm_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let wasm_path = wasm_dir.path().join("python-3.11.7.wasm");
+        std::fs::write(&wasm_path, b"local wasm bytes").unwrap();
+
+        // Offline mode only needs to block a request that has no choice
+        // but to hit a CDN; a Local source needs no network at all.
+        let loader = RuntimeLoader::builder()
+            .cache_dir(cache_dir.path().to_path_buf())
+            .offline(true)
+            .sources(vec![RuntimeSource::Local {
+                path: wasm_path.clone(),
+            }])
             .build()
             .unwrap();
 
-        assert_eq!(loader.cdn_sources.len(), 1);
+        let runtime = loader
+            .download_runtime("python", "3.11.7")
+            .await
+            .expect("offline mode should not block a Local-only source");
+        assert_eq!(runtime.version, "3.11.7");
+        assert_eq!(std::fs::read(&runtime.path).unwrap(), b"local wasm bytes");
     }
 
     #[test]
@@ -377,7 +994,219 @@ mod tests {
 
         assert!(loader
             .cache
-            .get_path(Language::Python, "3.11.7")
+            .get_path("python", "3.11.7")
             .starts_with(temp_dir.path()));
     }
+
+    #[test]
+    fn test_canonical_language_resolves_alias() {
+        let loader = RuntimeLoader::new().unwrap();
+        assert_eq!(loader.canonical_language("py"), "python");
+        assert_eq!(loader.canonical_language("python"), "python");
+        assert_eq!(loader.canonical_language("lua"), "lua");
+    }
+
+    #[test]
+    fn test_runtime_source_serde_roundtrip() {
+        let source = RuntimeSource::Git {
+            remote: "https://example.com/repo.git".to_string(),
+            revision: "abc123".to_string(),
+            subpath: Some(PathBuf::from("dist/python-3.11.7.wasm")),
+        };
+        let toml = toml::to_string(&source).unwrap();
+        let parsed: RuntimeSource = toml::from_str(&toml).unwrap();
+        assert_eq!(source, parsed);
+
+        let toml = toml::to_string(&RuntimeSource::GitHubReleases).unwrap();
+        assert!(toml.contains("github-releases"));
+    }
+
+    #[test]
+    fn test_builder_language_filter_blocks_disallowed_language() {
+        let loader = RuntimeLoader::builder()
+            .language_filter(LanguageFilter::Only {
+                only: vec!["python".to_string()],
+            })
+            .build()
+            .unwrap();
+
+        assert!(loader.check_language_allowed("python").is_ok());
+        assert!(matches!(
+            loader.check_language_allowed("ruby"),
+            Err(Error::LanguageNotAllowed(lang)) if lang == "ruby"
+        ));
+    }
+
+    #[test]
+    fn test_builder_config_path_missing_file_errors() {
+        let err = RuntimeLoader::builder()
+            .config_path(PathBuf::from("/nonexistent/wasmhub.toml"))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::Io(_)));
+    }
+
+    #[tokio::test]
+    async fn test_offline_get_runtime_errors_on_cache_miss() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+
+        let loader = RuntimeLoader::builder()
+            .cache_dir(temp_dir.path().to_path_buf())
+            .offline(true)
+            .build()
+            .unwrap();
+
+        let err = loader
+            .get_runtime("python", "3.11.7")
+            .await
+            .expect_err("offline mode should not reach the network");
+        assert!(matches!(err, Error::Offline(_)));
+    }
+
+    #[tokio::test]
+    async fn test_offline_get_runtime_serves_cached_version() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+
+        let loader = RuntimeLoader::builder()
+            .cache_dir(temp_dir.path().to_path_buf())
+            .offline(true)
+            .build()
+            .unwrap();
+        loader
+            .cache
+            .store("python", "3.11.7", b"cached wasm bytes")
+            .unwrap();
+
+        let runtime = loader
+            .get_runtime("python", "3.11.7")
+            .await
+            .expect("a cached exact-version match should be served without the network");
+        assert_eq!(runtime.version, "3.11.7");
+    }
+
+    #[tokio::test]
+    async fn test_install_custom_runtimes_registers_local_source() {
+        use crate::custom::CustomRuntimeSource;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let wasm_path = temp_dir.path().join("lua-5.4.6.wasm");
+        std::fs::write(&wasm_path, b"custom lua bytes").unwrap();
+
+        let loader = RuntimeLoader::builder()
+            .cache_dir(cache_dir.path().to_path_buf())
+            .custom_runtimes(vec![CustomRuntime {
+                language: "lua".to_string(),
+                version: "5.4.6".to_string(),
+                source: CustomRuntimeSource::Local { path: wasm_path },
+            }])
+            .build()
+            .unwrap();
+
+        let installed = loader
+            .install_custom_runtimes()
+            .await
+            .expect("custom runtimes should install");
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].language, "lua");
+
+        let cached = loader.cache.get("lua", "5.4.6").expect("should be cached");
+        assert_eq!(cached.sha256, installed[0].sha256);
+
+        let provenance = loader
+            .cache
+            .get_provenance("lua", "5.4.6")
+            .expect("provenance should be recorded");
+        assert!(provenance.source.starts_with("local:"));
+        assert_eq!(provenance.revision, None);
+    }
+
+    #[test]
+    fn test_sources_getter_matches_default() {
+        let loader = RuntimeLoader::new().unwrap();
+        assert_eq!(
+            loader.sources(),
+            &[RuntimeSource::GitHubReleases, RuntimeSource::JsDelivr]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_source_reachability_git_and_local_always_true() {
+        let loader = RuntimeLoader::new().unwrap();
+        let git = RuntimeSource::Git {
+            remote: "https://example.com/repo.git".to_string(),
+            revision: "abc123".to_string(),
+            subpath: None,
+        };
+        let local = RuntimeSource::Local {
+            path: PathBuf::from("/tmp/python-3.11.7.wasm"),
+        };
+        assert!(loader.check_source_reachability(&git).await);
+        assert!(loader.check_source_reachability(&local).await);
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let now = SystemTime::now();
+        assert!(RuntimeLoader::is_fresh(now, Duration::from_secs(60)));
+        assert!(!RuntimeLoader::is_fresh(now, Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_global_manifest_offline_uses_cache() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+
+        let loader = RuntimeLoader::builder()
+            .cache_dir(temp_dir.path().to_path_buf())
+            .offline(true)
+            .build()
+            .unwrap();
+        loader
+            .cache
+            .store_cached_manifest("global", "{\"languages\":{}}")
+            .unwrap();
+
+        let manifest = loader
+            .fetch_global_manifest()
+            .await
+            .expect("a cached manifest should satisfy an offline fetch");
+        assert_eq!(manifest.languages.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version_matching_applies_range() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+
+        let loader = RuntimeLoader::builder()
+            .cache_dir(temp_dir.path().to_path_buf())
+            .offline(true)
+            .build()
+            .unwrap();
+
+        let manifest_json = r#"{
+            "language": "python",
+            "versions": {
+                "3.11.7": {"file": "python-3.11.7.wasm", "size": 1, "sha256": "a", "released": "2024-01-01", "url": "https://example.com/3.11.7"},
+                "3.12.1": {"file": "python-3.12.1.wasm", "size": 1, "sha256": "b", "released": "2024-02-01", "url": "https://example.com/3.12.1"}
+            }
+        }"#;
+        loader
+            .cache
+            .store_cached_manifest("python", manifest_json)
+            .unwrap();
+
+        // Pin to the 3.11 line: the unconstrained latest is 3.12.1, but the
+        // matching variant should stop at the highest version in range.
+        let req = VersionReq::parse("~3.11").unwrap();
+        let version = loader
+            .get_latest_version_matching("python", &req)
+            .await
+            .expect("a version in range should resolve");
+        assert_eq!(version, "3.11.7");
+    }
 }
@@ -0,0 +1,119 @@
+//! `wasmhub.lock`: the concrete, verified result of resolving `wasmhub.toml`'s
+//! `[runtimes]` constraints, the same role a `Cargo.lock` plays for crate
+//! version requirements. Each `[[runtime]]` entry pins a language to an
+//! exact version plus the artifact's size and sha256, so `sync` can
+//! reproduce the same install on a fresh machine instead of re-resolving
+//! "latest" every time.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const LOCK_FILE_NAME: &str = "wasmhub.lock";
+
+/// A single resolved-and-verified runtime entry in a `wasmhub.lock`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LockedRuntime {
+    pub language: String,
+    pub version: String,
+    pub file: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// The parsed contents of a `wasmhub.lock`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LockFile {
+    #[serde(default, rename = "runtime")]
+    pub runtimes: Vec<LockedRuntime>,
+}
+
+impl LockFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        toml::from_str(&contents)
+            .map_err(|e| Error::Other(format!("Invalid lockfile at {}: {e}", path.display())))
+    }
+
+    /// Loads `path` if it exists, or `None` if there's nothing to restore
+    /// from yet.
+    pub fn load_if_exists(path: &Path) -> Result<Option<Self>> {
+        if path.is_file() {
+            Ok(Some(Self::load(path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize lockfile: {e}")))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn get(&self, language: &str) -> Option<&LockedRuntime> {
+        self.runtimes.iter().find(|entry| entry.language == language)
+    }
+
+    /// Inserts `entry`, replacing any existing lock for the same language.
+    pub fn set(&mut self, entry: LockedRuntime) {
+        match self
+            .runtimes
+            .iter_mut()
+            .find(|existing| existing.language == entry.language)
+        {
+            Some(existing) => *existing = entry,
+            None => self.runtimes.push(entry),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_entry() -> LockedRuntime {
+        LockedRuntime {
+            language: "python".to_string(),
+            version: "3.11.7".to_string(),
+            file: "python-3.11.7.wasm".to_string(),
+            size: 1024,
+            sha256: "abc123".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lock_file_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOCK_FILE_NAME);
+
+        let mut lock = LockFile::default();
+        lock.set(sample_entry());
+        lock.save(&path).expect("Failed to save lockfile");
+
+        let loaded = LockFile::load(&path).expect("Failed to load lockfile");
+        assert_eq!(loaded.get("python"), Some(&sample_entry()));
+    }
+
+    #[test]
+    fn test_set_replaces_existing_entry() {
+        let mut lock = LockFile::default();
+        lock.set(sample_entry());
+
+        let mut updated = sample_entry();
+        updated.version = "3.12.0".to_string();
+        lock.set(updated.clone());
+
+        assert_eq!(lock.runtimes.len(), 1);
+        assert_eq!(lock.get("python"), Some(&updated));
+    }
+
+    #[test]
+    fn test_load_if_exists_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOCK_FILE_NAME);
+        assert_eq!(LockFile::load_if_exists(&path).unwrap(), None);
+    }
+}
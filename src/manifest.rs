@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -75,6 +77,234 @@ impl RuntimeInfo {
             self.versions.push(version);
         }
     }
+
+    /// Resolves a loose version specifier (`"latest"`, `"lts"`, a prefix like
+    /// `"3.11"`, or a comparator range like `">=3.10,<3.12"`) to a concrete
+    /// version string.
+    ///
+    /// Candidates are drawn from `self.versions` plus `cached_versions`
+    /// (typically the result of `CacheManager::list` for this language). When
+    /// multiple candidates tie on version, a cached one is preferred so
+    /// resolution doesn't force a needless download.
+    pub fn resolve_version(
+        &self,
+        language: &str,
+        spec: &str,
+        cached_versions: &[String],
+    ) -> Result<String> {
+        match spec {
+            "latest" => Ok(self.latest.clone()),
+            "lts" => self.lts.clone().ok_or_else(|| Error::NoVersionMatches {
+                language: language.to_string(),
+                spec: spec.to_string(),
+                available: self.versions.join(", "),
+            }),
+            _ => {
+                let candidates = Self::collect_candidates(&self.versions, cached_versions);
+                let matcher = VersionMatcher::parse(spec).ok_or_else(|| Error::NoVersionMatches {
+                    language: language.to_string(),
+                    spec: spec.to_string(),
+                    available: self.versions.join(", "),
+                })?;
+
+                let mut best: Option<&Candidate> = None;
+                for candidate in &candidates {
+                    if !matcher.matches(&candidate.parsed) {
+                        continue;
+                    }
+                    best = Some(match best {
+                        None => candidate,
+                        Some(current) => {
+                            if candidate.parsed > current.parsed
+                                || (candidate.parsed == current.parsed
+                                    && candidate.cached
+                                    && !current.cached)
+                            {
+                                candidate
+                            } else {
+                                current
+                            }
+                        }
+                    });
+                }
+
+                best.map(|c| c.version.clone())
+                    .ok_or_else(|| Error::NoVersionMatches {
+                        language: language.to_string(),
+                        spec: spec.to_string(),
+                        available: self.versions.join(", "),
+                    })
+            }
+        }
+    }
+
+    fn collect_candidates(versions: &[String], cached_versions: &[String]) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = Vec::new();
+
+        for version in versions {
+            if let Some(parsed) = parse_loose_version(version) {
+                candidates.push(Candidate {
+                    version: version.clone(),
+                    parsed,
+                    cached: cached_versions.contains(version),
+                });
+            }
+        }
+
+        for version in cached_versions {
+            if candidates.iter().any(|c| &c.version == version) {
+                continue;
+            }
+            if let Some(parsed) = parse_loose_version(version) {
+                candidates.push(Candidate {
+                    version: version.clone(),
+                    parsed,
+                    cached: true,
+                });
+            }
+        }
+
+        candidates
+    }
+}
+
+struct Candidate {
+    version: String,
+    parsed: Version,
+    cached: bool,
+}
+
+/// Parses `s` as a `semver::Version`, filling in missing `minor`/`patch`
+/// components with `0` first (`"3"` -> `"3.0.0"`, `"3.11"` -> `"3.11.0"`) so
+/// the loose, dash-free version strings this manifest format uses (as
+/// opposed to the `semver::VersionReq` syntax [`RuntimeManifest::resolve_requirement`]
+/// consumes) still parse. Delegating the actual parsing/ordering to
+/// `semver::Version` keeps this in step with the comparator logic below
+/// instead of hand-rolling a second ad hoc numeric comparison.
+fn parse_loose_version(s: &str) -> Option<Version> {
+    let mut parts = s.trim().splitn(3, '.');
+    let major = parts.next()?;
+    let minor = parts.next().unwrap_or("0");
+    let patch = parts.next().unwrap_or("0");
+    Version::parse(&format!("{major}.{minor}.{patch}")).ok()
+}
+
+/// A `major[.minor[.patch]]` prefix where missing components act as
+/// wildcards, e.g. `"3.11"` matches any `3.11.x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PartialVersion {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl PartialVersion {
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map(|p| p.parse()).transpose().ok()?;
+        let patch = parts.next().map(|p| p.parse()).transpose().ok()?;
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        if self.major != version.major {
+            return false;
+        }
+        if let Some(minor) = self.minor {
+            if minor != version.minor {
+                return false;
+            }
+        }
+        if let Some(patch) = self.patch {
+            if patch != version.patch {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparatorOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: ComparatorOp,
+    version: Version,
+}
+
+impl Comparator {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (op, rest) = if let Some(r) = s.strip_prefix(">=") {
+            (ComparatorOp::Ge, r)
+        } else if let Some(r) = s.strip_prefix("<=") {
+            (ComparatorOp::Le, r)
+        } else if let Some(r) = s.strip_prefix('>') {
+            (ComparatorOp::Gt, r)
+        } else if let Some(r) = s.strip_prefix('<') {
+            (ComparatorOp::Lt, r)
+        } else if let Some(r) = s.strip_prefix('=') {
+            (ComparatorOp::Eq, r)
+        } else {
+            (ComparatorOp::Eq, s)
+        };
+
+        Some(Self {
+            op,
+            version: parse_loose_version(rest.trim())?,
+        })
+    }
+
+    fn satisfied_by(&self, version: &Version) -> bool {
+        match self.op {
+            ComparatorOp::Gt => version > &self.version,
+            ComparatorOp::Ge => version >= &self.version,
+            ComparatorOp::Lt => version < &self.version,
+            ComparatorOp::Le => version <= &self.version,
+            ComparatorOp::Eq => version == &self.version,
+        }
+    }
+}
+
+/// Either a wildcard prefix (`"3.11"`) or a comparator range
+/// (`">=3.10,<3.12"`), used to filter candidate versions during resolution.
+enum VersionMatcher {
+    Prefix(PartialVersion),
+    Range(Vec<Comparator>),
+}
+
+impl VersionMatcher {
+    fn parse(spec: &str) -> Option<Self> {
+        if spec.contains(['>', '<', '=', ',']) {
+            spec.split(',')
+                .map(Comparator::parse)
+                .collect::<Option<Vec<_>>>()
+                .map(VersionMatcher::Range)
+        } else {
+            PartialVersion::parse(spec).map(VersionMatcher::Prefix)
+        }
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            VersionMatcher::Prefix(partial) => partial.matches(version),
+            VersionMatcher::Range(comparators) => {
+                comparators.iter().all(|c| c.satisfied_by(version))
+            }
+        }
+    }
 }
 
 impl RuntimeManifest {
@@ -92,6 +322,19 @@ impl RuntimeManifest {
     pub fn get_version(&self, version: &str) -> Option<&RuntimeVersion> {
         self.versions.get(version)
     }
+
+    /// Finds the highest published version satisfying `req`, parsing each
+    /// key as a `semver::Version` and skipping anything that doesn't parse
+    /// (e.g. a legacy non-semver tag). Returns the matching version string
+    /// alongside its `RuntimeVersion` metadata.
+    pub fn resolve_requirement(&self, req: &VersionReq) -> Option<(&str, &RuntimeVersion)> {
+        self.versions
+            .iter()
+            .filter_map(|(key, info)| Version::parse(key).ok().map(|parsed| (parsed, key, info)))
+            .filter(|(parsed, _, _)| req.matches(parsed))
+            .max_by(|(a, _, _), (b, _, _)| a.cmp(b))
+            .map(|(_, key, info)| (key.as_str(), info))
+    }
 }
 
 impl RuntimeVersion {
@@ -232,4 +475,132 @@ mod tests {
         let parsed: GlobalManifest = serde_json::from_str(&json).unwrap();
         assert_eq!(manifest, parsed);
     }
+
+    fn python_info() -> RuntimeInfo {
+        let mut info = RuntimeInfo::new(
+            "3.12.0".to_string(),
+            "https://github.com/pyodide/pyodide".to_string(),
+            "MIT".to_string(),
+        )
+        .with_lts("3.10.13".to_string());
+        for version in ["3.10.13", "3.11.6", "3.11.7", "3.12.0"] {
+            info.add_version(version.to_string());
+        }
+        info
+    }
+
+    #[test]
+    fn test_resolve_version_latest_and_lts() {
+        let info = python_info();
+        assert_eq!(
+            info.resolve_version("python", "latest", &[]).unwrap(),
+            "3.12.0"
+        );
+        assert_eq!(
+            info.resolve_version("python", "lts", &[]).unwrap(),
+            "3.10.13"
+        );
+    }
+
+    #[test]
+    fn test_resolve_version_prefix() {
+        let info = python_info();
+        assert_eq!(
+            info.resolve_version("python", "3.11", &[]).unwrap(),
+            "3.11.7"
+        );
+        assert_eq!(
+            info.resolve_version("python", "3", &[]).unwrap(),
+            "3.12.0"
+        );
+    }
+
+    #[test]
+    fn test_resolve_version_range() {
+        let info = python_info();
+        assert_eq!(
+            info.resolve_version("python", ">=3.10,<3.12", &[])
+                .unwrap(),
+            "3.11.7"
+        );
+    }
+
+    #[test]
+    fn test_resolve_version_no_match() {
+        let info = python_info();
+        let err = info.resolve_version("python", "9.9", &[]).unwrap_err();
+        assert!(err.to_string().contains("No version matches"));
+        assert!(err.to_string().contains("3.12.0"));
+    }
+
+    #[test]
+    fn test_resolve_version_prefers_cached_on_tie() {
+        let info = python_info();
+        // The cached "3.12" and the published "3.12.0" both parse as 3.12.0;
+        // the cached spelling wins the tie.
+        let cached = vec!["3.12".to_string()];
+        assert_eq!(
+            info.resolve_version("python", "3.12", &cached).unwrap(),
+            "3.12"
+        );
+    }
+
+    fn python_runtime_manifest() -> RuntimeManifest {
+        let mut manifest = RuntimeManifest::new("python".to_string());
+        for version in ["3.10.13", "3.11.6", "3.11.7", "3.12.0"] {
+            manifest.add_version(
+                version.to_string(),
+                RuntimeVersion::new(
+                    format!("python-{version}.wasm"),
+                    1024,
+                    "abc123".to_string(),
+                    "2024-01-01".to_string(),
+                    format!("https://example.com/python-{version}.wasm"),
+                ),
+            );
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_resolve_requirement_picks_highest_match() {
+        let manifest = python_runtime_manifest();
+        let req = VersionReq::parse(">=3.11.0, <3.12.0").unwrap();
+        let (version, _) = manifest.resolve_requirement(&req).unwrap();
+        assert_eq!(version, "3.11.7");
+    }
+
+    #[test]
+    fn test_resolve_requirement_caret() {
+        let manifest = python_runtime_manifest();
+        let req = VersionReq::parse("^3.11").unwrap();
+        let (version, _) = manifest.resolve_requirement(&req).unwrap();
+        assert_eq!(version, "3.12.0");
+    }
+
+    #[test]
+    fn test_resolve_requirement_no_match() {
+        let manifest = python_runtime_manifest();
+        let req = VersionReq::parse("^9.9").unwrap();
+        assert!(manifest.resolve_requirement(&req).is_none());
+    }
+
+    #[test]
+    fn test_resolve_requirement_skips_unparseable_keys() {
+        let mut manifest = python_runtime_manifest();
+        manifest.add_version(
+            "unstable".to_string(),
+            RuntimeVersion::new(
+                "python-unstable.wasm".to_string(),
+                1024,
+                "abc123".to_string(),
+                "2024-01-01".to_string(),
+                "https://example.com/python-unstable.wasm".to_string(),
+            ),
+        );
+
+        let req = VersionReq::parse("*").unwrap();
+        let (version, _) = manifest.resolve_requirement(&req).unwrap();
+        assert_eq!(version, "3.12.0");
+    }
 }
@@ -0,0 +1,158 @@
+//! Per-project runtime version pinning via a `.wasm-runtime` file —
+//! `language = "version"` pairs, the same ergonomics a language version
+//! manager provides so a repo checkout deterministically selects the
+//! intended runtime without typing `latest` every time.
+
+use crate::error::{Error, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const PIN_FILE_NAME: &str = ".wasm-runtime";
+
+/// A parsed `.wasm-runtime` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PinFile {
+    pub pins: BTreeMap<String, String>,
+}
+
+impl PinFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let pins: BTreeMap<String, String> = toml::from_str(&contents)
+            .map_err(|e| Error::Other(format!("Invalid pin file at {}: {e}", path.display())))?;
+        Ok(Self { pins })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(&self.pins)
+            .map_err(|e| Error::Other(format!("Failed to serialize pin file: {e}")))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn get(&self, language: &str) -> Option<&str> {
+        self.pins.get(language).map(String::as_str)
+    }
+
+    pub fn set(&mut self, language: impl Into<String>, version: impl Into<String>) {
+        self.pins.insert(language.into(), version.into());
+    }
+
+    /// Walks upward from `start_dir` looking for a `.wasm-runtime` file,
+    /// the same ascent a `.gitignore`/`.editorconfig` lookup performs.
+    pub fn find_project_file(start_dir: &Path) -> Option<PathBuf> {
+        let mut dir = Some(start_dir);
+        while let Some(current) = dir {
+            let candidate = current.join(PIN_FILE_NAME);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            dir = current.parent();
+        }
+        None
+    }
+
+    /// Loads the nearest project `.wasm-runtime` file at or above
+    /// `start_dir`, if any.
+    pub fn load_nearest(start_dir: &Path) -> Result<Option<Self>> {
+        match Self::find_project_file(start_dir) {
+            Some(path) => Ok(Some(Self::load(&path)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The fallback pin file written by `pin --global`, consulted when no
+    /// project file is found: `<config dir>/wasm-runtime/.wasm-runtime`.
+    pub fn global_path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| Error::Other("Could not determine config directory".to_string()))?
+            .join("wasm-runtime");
+        Ok(config_dir.join(PIN_FILE_NAME))
+    }
+
+    /// Loads the global pin file, if one has been written.
+    pub fn load_global() -> Result<Option<Self>> {
+        let path = Self::global_path()?;
+        if path.is_file() {
+            Ok(Some(Self::load(&path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Resolves a pinned version for `language`, preferring the nearest
+    /// project file over the global fallback. Returns `None` if neither
+    /// pins `language`.
+    pub fn resolve(start_dir: &Path, language: &str) -> Result<Option<String>> {
+        if let Some(pin) = Self::load_nearest(start_dir)? {
+            if let Some(version) = pin.get(language) {
+                return Ok(Some(version.to_string()));
+            }
+        }
+        if let Some(pin) = Self::load_global()? {
+            if let Some(version) = pin.get(language) {
+                return Ok(Some(version.to_string()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pin_file_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(PIN_FILE_NAME);
+
+        let mut pin = PinFile::default();
+        pin.set("python", "3.11.7");
+        pin.save(&path).expect("Failed to save pin file");
+
+        let loaded = PinFile::load(&path).expect("Failed to load pin file");
+        assert_eq!(loaded.get("python"), Some("3.11.7"));
+    }
+
+    #[test]
+    fn test_find_project_file_walks_up() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b").join("c");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let mut pin = PinFile::default();
+        pin.set("python", "3.11.7");
+        pin.save(&temp_dir.path().join(PIN_FILE_NAME)).unwrap();
+
+        let found = PinFile::find_project_file(&nested);
+        assert_eq!(found, Some(temp_dir.path().join(PIN_FILE_NAME)));
+    }
+
+    #[test]
+    fn test_find_project_file_missing_returns_none() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(PinFile::find_project_file(temp_dir.path()), None);
+    }
+
+    #[test]
+    fn test_resolve_reads_project_pin() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut project_pin = PinFile::default();
+        project_pin.set("python", "3.11.7");
+        project_pin
+            .save(&temp_dir.path().join(PIN_FILE_NAME))
+            .unwrap();
+
+        let resolved = PinFile::resolve(temp_dir.path(), "python").unwrap();
+        assert_eq!(resolved, Some("3.11.7".to_string()));
+
+        let resolved = PinFile::resolve(temp_dir.path(), "ruby").unwrap();
+        assert_eq!(resolved, None);
+    }
+}
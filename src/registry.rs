@@ -0,0 +1,140 @@
+//! An open, data-driven registry of runtime languages.
+//!
+//! `Language` remains a fixed enum of the six built-in runtimes, but the
+//! cache and loader key off a language *slug* (`&str`) rather than the enum
+//! directly, so a [`LanguageRegistry`] can add custom runtimes — declared
+//! via data loaded from a `GlobalManifest` or a user config file — without
+//! forking the crate.
+
+use crate::runtime::Language;
+
+/// A single registered language: its canonical slug, human-readable name,
+/// the aliases `FromStr`-style lookups should accept, and default
+/// source/license metadata for manifest entries that don't specify their
+/// own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageDescriptor {
+    pub slug: String,
+    pub display_name: String,
+    pub aliases: Vec<String>,
+    pub default_source: String,
+    pub default_license: String,
+}
+
+impl LanguageDescriptor {
+    pub fn new(slug: impl Into<String>, display_name: impl Into<String>) -> Self {
+        Self {
+            slug: slug.into(),
+            display_name: display_name.into(),
+            aliases: Vec::new(),
+            default_source: String::new(),
+            default_license: String::new(),
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    pub fn with_defaults(mut self, source: impl Into<String>, license: impl Into<String>) -> Self {
+        self.default_source = source.into();
+        self.default_license = license.into();
+        self
+    }
+}
+
+/// A registry of known languages: the six built-ins plus any custom
+/// runtimes added via [`LanguageRegistry::register`].
+#[derive(Debug, Clone)]
+pub struct LanguageRegistry {
+    descriptors: Vec<LanguageDescriptor>,
+}
+
+impl LanguageRegistry {
+    /// A registry containing just the six built-in languages.
+    pub fn with_builtins() -> Self {
+        Self {
+            descriptors: Language::all().iter().map(|lang| lang.descriptor()).collect(),
+        }
+    }
+
+    /// An empty registry with no built-ins, for callers that want to
+    /// declare every language themselves.
+    pub fn empty() -> Self {
+        Self {
+            descriptors: Vec::new(),
+        }
+    }
+
+    /// Registers `descriptor`, replacing any existing entry with the same
+    /// slug (this is how a custom runtime overrides a built-in default).
+    pub fn register(&mut self, descriptor: LanguageDescriptor) {
+        self.descriptors.retain(|d| d.slug != descriptor.slug);
+        self.descriptors.push(descriptor);
+    }
+
+    pub fn get(&self, slug: &str) -> Option<&LanguageDescriptor> {
+        self.descriptors.iter().find(|d| d.slug == slug)
+    }
+
+    /// Resolves a user-supplied name or alias (case-insensitive) to its
+    /// canonical slug.
+    pub fn resolve(&self, input: &str) -> Option<&str> {
+        let needle = input.to_lowercase();
+        self.descriptors
+            .iter()
+            .find(|d| d.slug == needle || d.aliases.iter().any(|alias| alias == &needle))
+            .map(|d| d.slug.as_str())
+    }
+
+    /// All registered languages: built-ins plus anything added via
+    /// [`LanguageRegistry::register`].
+    pub fn all(&self) -> &[LanguageDescriptor] {
+        &self.descriptors
+    }
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_builtins_resolves_aliases() {
+        let registry = LanguageRegistry::with_builtins();
+        assert_eq!(registry.resolve("python"), Some("python"));
+        assert_eq!(registry.resolve("py"), Some("python"));
+        assert_eq!(registry.resolve("node.js"), Some("nodejs"));
+        assert_eq!(registry.resolve("unknown"), None);
+    }
+
+    #[test]
+    fn test_register_custom_language() {
+        let mut registry = LanguageRegistry::with_builtins();
+        registry.register(
+            LanguageDescriptor::new("lua", "Lua")
+                .with_aliases(vec!["lua".to_string()])
+                .with_defaults("https://github.com/lua/lua", "MIT"),
+        );
+
+        assert_eq!(registry.resolve("lua"), Some("lua"));
+        assert_eq!(registry.all().len(), Language::all().len() + 1);
+    }
+
+    #[test]
+    fn test_register_overrides_existing_slug() {
+        let mut registry = LanguageRegistry::with_builtins();
+        let before = registry.all().len();
+
+        registry.register(LanguageDescriptor::new("python", "CPython (fork)"));
+
+        assert_eq!(registry.all().len(), before);
+        assert_eq!(registry.get("python").unwrap().display_name, "CPython (fork)");
+    }
+}
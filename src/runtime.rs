@@ -1,3 +1,4 @@
+use crate::registry::LanguageDescriptor;
 use std::fmt;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -24,6 +25,9 @@ impl Language {
         }
     }
 
+    /// The built-in languages the crate ships with. For the full set
+    /// including runtimes registered at runtime, see
+    /// [`crate::registry::LanguageRegistry::all`].
     pub fn all() -> &'static [Language] {
         &[
             Language::NodeJs,
@@ -34,6 +38,21 @@ impl Language {
             Language::Rust,
         ]
     }
+
+    /// The registry descriptor (slug, display name, aliases) for this
+    /// built-in language, used to seed `LanguageRegistry::with_builtins`.
+    pub fn descriptor(&self) -> LanguageDescriptor {
+        let (display_name, aliases): (&str, &[&str]) = match self {
+            Language::NodeJs => ("Node.js", &["nodejs", "node", "node.js"]),
+            Language::Python => ("Python", &["python", "py"]),
+            Language::Ruby => ("Ruby", &["ruby", "rb"]),
+            Language::Php => ("PHP", &["php"]),
+            Language::Go => ("Go", &["go", "golang"]),
+            Language::Rust => ("Rust", &["rust", "rs"]),
+        };
+        LanguageDescriptor::new(self.as_str(), display_name)
+            .with_aliases(aliases.iter().map(|alias| alias.to_string()).collect())
+    }
 }
 
 impl FromStr for Language {
@@ -58,9 +77,13 @@ impl fmt::Display for Language {
     }
 }
 
+/// A cached runtime. `language` is the registry slug (e.g. `"python"`, or a
+/// custom slug registered via `LanguageRegistry`) rather than the built-in
+/// `Language` enum, so the cache directory layout stays open to runtimes
+/// that aren't one of the six built-ins.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Runtime {
-    pub language: Language,
+    pub language: String,
     pub version: String,
     pub path: PathBuf,
     pub size: u64,
@@ -69,14 +92,14 @@ pub struct Runtime {
 
 impl Runtime {
     pub fn new(
-        language: Language,
+        language: impl Into<String>,
         version: String,
         path: PathBuf,
         size: u64,
         sha256: String,
     ) -> Self {
         Self {
-            language,
+            language: language.into(),
             version,
             path,
             size,
@@ -85,7 +108,7 @@ impl Runtime {
     }
 
     pub fn filename(&self) -> String {
-        format!("{}-{}.wasm", self.language.as_str(), self.version)
+        format!("{}-{}.wasm", self.language, self.version)
     }
 }
 
@@ -131,14 +154,14 @@ mod tests {
     #[test]
     fn test_runtime_new() {
         let runtime = Runtime::new(
-            Language::Python,
+            Language::Python.as_str(),
             "3.11.7".to_string(),
             PathBuf::from("/cache/python-3.11.7.wasm"),
             1024,
             "abc123".to_string(),
         );
 
-        assert_eq!(runtime.language, Language::Python);
+        assert_eq!(runtime.language, "python");
         assert_eq!(runtime.version, "3.11.7");
         assert_eq!(runtime.size, 1024);
         assert_eq!(runtime.sha256, "abc123");
@@ -147,7 +170,7 @@ mod tests {
     #[test]
     fn test_runtime_filename() {
         let runtime = Runtime::new(
-            Language::NodeJs,
+            Language::NodeJs.as_str(),
             "20.2.0".to_string(),
             PathBuf::from("/cache/nodejs-20.2.0.wasm"),
             2048,
@@ -157,6 +180,20 @@ mod tests {
         assert_eq!(runtime.filename(), "nodejs-20.2.0.wasm");
     }
 
+    #[test]
+    fn test_runtime_with_custom_language_slug() {
+        let runtime = Runtime::new(
+            "lua",
+            "5.4.6".to_string(),
+            PathBuf::from("/cache/lua-5.4.6.wasm"),
+            512,
+            "ghi789".to_string(),
+        );
+
+        assert_eq!(runtime.language, "lua");
+        assert_eq!(runtime.filename(), "lua-5.4.6.wasm");
+    }
+
     #[test]
     fn test_language_all() {
         let languages = Language::all();